@@ -0,0 +1,40 @@
+//! Compares matching links through erased and typed value requests
+//!
+//! Run with `cargo bench --bench matches`.
+
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use datalink::query::{DataFilter, Filter, LinkFilter};
+
+const RUNS: u32 = 200;
+
+fn bench(name: &str, mut f: impl FnMut() -> usize) -> Duration {
+    black_box(f());
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        black_box(f());
+    }
+    let elapsed = start.elapsed() / RUNS;
+    println!("{name:>8}: {elapsed:?} per run");
+    elapsed
+}
+
+fn main() {
+    let map: HashMap<String, u64> = (0..10_000).map(|i| (format!("key{i}"), i)).collect();
+    let filter = black_box(
+        LinkFilter::key(DataFilter::prefix("key1")) & LinkFilter::target(DataFilter::gt(5_000u64)),
+    );
+
+    let erased = bench("erased", || {
+        map.iter().filter(|link| filter.matches(link)).count()
+    });
+    let typed = bench("typed", || {
+        map.iter().filter(|link| filter.matches_typed(link)).count()
+    });
+    println!(
+        "speedup: {:.2}x",
+        erased.as_secs_f64() / typed.as_secs_f64()
+    );
+}
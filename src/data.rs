@@ -7,9 +7,17 @@ use crate::{
 
 #[cfg(feature = "unique")]
 pub mod constant;
+#[cfg(feature = "unique")]
+pub mod content;
 mod ext;
 pub mod format;
 mod impls;
+#[cfg(feature = "std")]
+pub mod indexed;
+#[cfg(feature = "std")]
+pub mod join;
+#[cfg(feature = "unique")]
+pub mod key;
 #[cfg(feature = "unique")]
 pub mod unique;
 
@@ -39,6 +47,13 @@ pub trait Data {
         Ok(())
     }
 
+    /// Pushes the links matching the filter of `query`
+    ///
+    /// The query's [`limit`](crate::query::Query::limit) and
+    /// [`selection`](crate::query::Query::selection) are only hints for reading fewer links;
+    /// more links may be pushed and targets are pushed unprojected. [`DataExt::query`],
+    /// [`DataExt::query_iter`], [`DataExt::aggregate`] and [`Plan`](crate::query::Plan)
+    /// apply both.
     #[allow(unused_variables)]
     #[inline]
     fn query_links(
@@ -54,6 +69,25 @@ pub trait Data {
         }
     }
 
+    /// Whether [`query_links`](Data::query_links) evaluates `filter` without scanning all links
+    ///
+    /// A [`Plan`](crate::query::Plan) only pushes down filters this returns `true` for and applies
+    /// the rest to the links it gets back, so `query_links` may reject all other filters with
+    /// [`LinkError::UnsupportedQuery`].
+    #[inline]
+    fn supports_filter(&self, filter: &crate::query::LinkFilter) -> bool {
+        use crate::query::LinkFilter;
+        matches!(filter, LinkFilter::Any | LinkFilter::None)
+    }
+
+    /// Iterator handing out the links one at a time, `None` if they can only be pushed
+    ///
+    /// [`DataExt::links`] collects the links of data returning `None`.
+    #[inline]
+    fn iter_links(&self) -> Option<crate::links::iter::LinkIter<'_>> {
+        None
+    }
+
     #[cfg_attr(not(feature = "unique"), doc(hidden))]
     #[inline]
     fn get_id(&self) -> Option<crate::id::ID> {
@@ -113,6 +113,17 @@ where
         (&self.0).query_links(links, query)
     }
 
+    #[inline]
+    fn supports_filter(&self, filter: &crate::query::LinkFilter) -> bool {
+        (&self.0).supports_filter(filter)
+    }
+
+    #[inline]
+    fn iter_links(&self) -> Option<crate::links::iter::LinkIter<'_>> {
+        // The inner data is only borrowed for this call, so its links are buffered instead
+        None
+    }
+
     #[inline(always)]
     fn get_id(&self) -> Option<ID> {
         ID::try_new(I).ok()
@@ -0,0 +1,461 @@
+use std::collections::HashMap;
+use std::num::NonZeroU128;
+
+use crate::data::{format, BoxedData, Data, DataExt};
+use crate::id::{FNV_OFFSET_BASIS, FNV_PRIME, ID};
+use crate::links::{LinkError, Links, Result, CONTINUE};
+use crate::rr::{meta::MetaInfo, Request};
+use crate::value::{AllValues, Value};
+
+/// Version of the digest format
+///
+/// Part of every digest, so digests of different versions never compare equal.
+/// Bump this whenever the way values or links are hashed changes.
+pub const VERSION: u8 = 2;
+
+/// Nesting depth after which hashing is aborted, to guard against cyclic data without `ID`s
+pub const MAX_DEPTH: usize = 256;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Data is nested deeper than {MAX_DEPTH} levels")]
+    TooDeep,
+    #[error("Data links back to itself")]
+    Cyclic,
+}
+
+/// Stable 128-bit digest of the values and links of `data`
+///
+/// Values are hashed in the order they are provided, unkeyed links in the order they are pushed.
+/// Keyed links are combined independently of their order, so maps with the same entries
+/// have the same digest regardless of their iteration order.
+/// `ID`s of the data itself are ignored, only the content counts.
+/// They are used to hash shared data only once and to detect cycles, as data with the same `ID` has the same content.
+///
+/// Values that are neither primitives nor known meta values only add a marker of their presence.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use datalink::data::content::digest;
+///
+/// let a = HashMap::from([("a", 1), ("b", 2)]);
+/// let b = HashMap::from([("b", 2), ("a", 1)]);
+///
+/// assert_eq!(digest(&a).unwrap(), digest(&b).unwrap());
+/// assert_ne!(digest(&vec![1, 2]).unwrap(), digest(&vec![2, 1]).unwrap());
+/// ```
+#[inline]
+pub fn digest(data: &(impl Data + ?Sized)) -> core::result::Result<u128, LinkError> {
+    digest_at(data, 0, &mut HashMap::new())
+}
+
+/// Content-addressed `ID` of `data`, see [`digest`]
+#[inline]
+pub fn content_id(data: &(impl Data + ?Sized)) -> core::result::Result<ID, LinkError> {
+    let digest = digest(data)?;
+    // Practically unreachable, but has to map somewhere, like `ID::from_name` does
+    let digest = NonZeroU128::new(digest).unwrap_or(NonZeroU128::MAX);
+    Ok(ID::from_raw(digest))
+}
+
+/// Digests of data with an `ID`, `None` while it is being hashed
+type Memo = HashMap<ID, Option<u128>>;
+
+fn digest_at(
+    data: &(impl Data + ?Sized),
+    depth: usize,
+    memo: &mut Memo,
+) -> core::result::Result<u128, LinkError> {
+    if depth > MAX_DEPTH {
+        return Err(LinkError::other(Error::TooDeep));
+    }
+    let id = data.get_id();
+    if let Some(id) = id {
+        match memo.insert(id, None) {
+            Some(Some(digest)) => {
+                memo.insert(id, Some(digest));
+                return Ok(digest);
+            }
+            Some(None) => return Err(LinkError::other(Error::Cyclic)),
+            None => {}
+        }
+    }
+
+    let mut hasher = Fnv::new();
+    hasher.write(b"datalink/content");
+    hasher.write_u8(VERSION);
+
+    let mut request = Request::<AllValues>::default();
+    data.provide_value(&mut request.as_erased());
+    for value in request.take() {
+        hash_value(&mut hasher, &value);
+    }
+
+    let mut links = LinkDigests {
+        depth: depth + 1,
+        memo,
+        unkeyed: Fnv::new(),
+        unkeyed_count: 0,
+        keyed: 0,
+        keyed_count: 0,
+    };
+    data.provide_links(&mut links)?;
+
+    hasher.write_u8(tag::LINKS);
+    hasher.write_u64(links.unkeyed_count);
+    hasher.write_u128(links.unkeyed.finish());
+    hasher.write_u64(links.keyed_count);
+    hasher.write_u128(links.keyed);
+
+    let digest = hasher.finish();
+    if let Some(id) = id {
+        links.memo.insert(id, Some(digest));
+    }
+    Ok(digest)
+}
+
+fn hash_value(hasher: &mut Fnv, value: &Value) {
+    match *value {
+        Value::True => hasher.write_bool(true),
+        Value::False => hasher.write_bool(false),
+        Value::Bool(v) => hasher.write_bool(v),
+        Value::U8(v) => hasher.write_tagged(tag::U8, &v.to_le_bytes()),
+        Value::I8(v) => hasher.write_tagged(tag::I8, &v.to_le_bytes()),
+        Value::U16(v) => hasher.write_tagged(tag::U16, &v.to_le_bytes()),
+        Value::I16(v) => hasher.write_tagged(tag::I16, &v.to_le_bytes()),
+        Value::U32(v) => hasher.write_tagged(tag::U32, &v.to_le_bytes()),
+        Value::I32(v) => hasher.write_tagged(tag::I32, &v.to_le_bytes()),
+        Value::U64(v) => hasher.write_tagged(tag::U64, &v.to_le_bytes()),
+        Value::I64(v) => hasher.write_tagged(tag::I64, &v.to_le_bytes()),
+        Value::U128(v) => hasher.write_tagged(tag::U128, &v.to_le_bytes()),
+        Value::I128(v) => hasher.write_tagged(tag::I128, &v.to_le_bytes()),
+        Value::F32(v) => {
+            let v = if v.is_nan() { f32::NAN } else { v };
+            hasher.write_tagged(tag::F32, &v.to_bits().to_le_bytes());
+        }
+        Value::F64(v) => {
+            let v = if v.is_nan() { f64::NAN } else { v };
+            hasher.write_tagged(tag::F64, &v.to_bits().to_le_bytes());
+        }
+        Value::Char(v) => hasher.write_tagged(tag::CHAR, &u32::from(v).to_le_bytes()),
+        Value::String(ref v) => hasher.write_sized(tag::STRING, v.as_bytes()),
+        Value::Bytes(ref v) => hasher.write_sized(tag::BYTES, v),
+        Value::Other(ref v) => match MetaInfo::about_val(v.as_ref()).name() {
+            Some(name) => hasher.write_sized(tag::META, name.as_bytes()),
+            // `TypeId`s differ between builds, so only the presence of a value is stable
+            None => hasher.write_u8(tag::OTHER),
+        },
+    }
+}
+
+struct LinkDigests<'m> {
+    depth: usize,
+    memo: &'m mut Memo,
+    unkeyed: Fnv,
+    unkeyed_count: u64,
+    keyed: u128,
+    keyed_count: u64,
+}
+
+impl Links for LinkDigests<'_> {
+    fn push(&mut self, target: BoxedData, key: Option<BoxedData>) -> Result {
+        let target = digest_at(target.as_ref(), self.depth, self.memo)?;
+
+        if let Some(key) = key {
+            let key = digest_at(key.as_ref(), self.depth, self.memo)?;
+
+            let mut entry = Fnv::new();
+            entry.write_u128(key);
+            entry.write_u128(target);
+            // Addition is commutative, so the order of keyed links doesn't matter
+            self.keyed = self.keyed.wrapping_add(entry.finish());
+            self.keyed_count += 1;
+        } else {
+            self.unkeyed.write_u128(target);
+            self.unkeyed_count += 1;
+        }
+
+        CONTINUE
+    }
+}
+
+mod tag {
+    pub(super) const BOOL: u8 = 1;
+    pub(super) const U8: u8 = 2;
+    pub(super) const I8: u8 = 3;
+    pub(super) const U16: u8 = 4;
+    pub(super) const I16: u8 = 5;
+    pub(super) const U32: u8 = 6;
+    pub(super) const I32: u8 = 7;
+    pub(super) const U64: u8 = 8;
+    pub(super) const I64: u8 = 9;
+    pub(super) const U128: u8 = 10;
+    pub(super) const I128: u8 = 11;
+    pub(super) const F32: u8 = 12;
+    pub(super) const F64: u8 = 13;
+    pub(super) const CHAR: u8 = 14;
+    pub(super) const STRING: u8 = 15;
+    pub(super) const BYTES: u8 = 16;
+    pub(super) const META: u8 = 17;
+    pub(super) const LINKS: u8 = 18;
+    pub(super) const OTHER: u8 = 19;
+}
+
+/// Incremental 128-bit FNV-1a, matching [`fnv1a_128`](crate::id::fnv1a_128)
+struct Fnv(u128);
+
+impl Fnv {
+    const fn new() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u128::from(byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u8(&mut self, v: u8) {
+        self.write(&[v]);
+    }
+
+    fn write_u64(&mut self, v: u64) {
+        self.write(&v.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, v: u128) {
+        self.write(&v.to_le_bytes());
+    }
+
+    fn write_bool(&mut self, v: bool) {
+        self.write(&[tag::BOOL, u8::from(v)]);
+    }
+
+    fn write_tagged(&mut self, tag: u8, bytes: &[u8]) {
+        self.write_u8(tag);
+        self.write(bytes);
+    }
+
+    /// Length prefixed, so adjacent values can't run into each other
+    fn write_sized(&mut self, tag: u8, bytes: &[u8]) {
+        self.write_u8(tag);
+        self.write_u64(bytes.len() as u64);
+        self.write(bytes);
+    }
+
+    const fn finish(&self) -> u128 {
+        self.0
+    }
+}
+
+/// Wrapper whose `ID` is derived from its content
+///
+/// The `ID` is computed once on construction, see [`digest`] for what goes into it.
+///
+/// ```rust
+/// use datalink::prelude::*;
+/// use datalink::data::content::ContentAddressed;
+///
+/// let a = ContentAddressed::new(vec!["foo", "bar"]).unwrap();
+/// let b = ContentAddressed::new(vec!["foo", "bar"]).unwrap();
+///
+/// assert_eq!(a.get_id(), b.get_id());
+/// ```
+pub struct ContentAddressed<D: Data> {
+    data: D,
+    id: ID,
+}
+
+impl<D: Data> ContentAddressed<D> {
+    #[inline]
+    pub fn new(data: D) -> core::result::Result<Self, LinkError> {
+        let id = content_id(&data)?;
+        Ok(Self { data, id })
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> D {
+        self.data
+    }
+}
+
+impl<D: Data> std::fmt::Debug for ContentAddressed<D> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.format::<format::DEBUG>().fmt(f)
+    }
+}
+
+impl<D: Data> AsRef<D> for ContentAddressed<D> {
+    /// Returns a reference to the underlying data
+    #[inline]
+    fn as_ref(&self) -> &D {
+        &self.data
+    }
+}
+
+#[warn(clippy::missing_trait_methods)]
+impl<D: Data> Data for ContentAddressed<D> {
+    #[inline]
+    fn provide_value(&self, request: &mut Request) {
+        self.data.provide_value(request);
+    }
+    #[inline]
+    fn provide_requested<Q: crate::rr::Query>(
+        &self,
+        request: &mut Request<Q>,
+    ) -> impl super::Provided
+    where
+        Self: Sized,
+    {
+        self.data.provide_requested(request)
+    }
+    #[inline]
+    fn provide_links(&self, links: &mut dyn Links) -> core::result::Result<(), LinkError> {
+        self.data.provide_links(links)
+    }
+    #[inline]
+    fn query_links(
+        &self,
+        links: &mut dyn Links,
+        query: &crate::query::Query,
+    ) -> core::result::Result<(), LinkError> {
+        self.data.query_links(links, query)
+    }
+    #[inline]
+    fn supports_filter(&self, filter: &crate::query::LinkFilter) -> bool {
+        self.data.supports_filter(filter)
+    }
+    #[inline]
+    fn iter_links(&self) -> Option<crate::links::iter::LinkIter<'_>> {
+        self.data.iter_links()
+    }
+    #[inline]
+    fn get_id(&self) -> Option<ID> {
+        Some(self.id)
+    }
+}
+
+impl<D: Data> crate::data::unique::Unique for ContentAddressed<D> {
+    #[inline]
+    fn id(&self) -> ID {
+        self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::data::unique::MaybeUnique;
+    use crate::links::LinksExt;
+
+    #[test]
+    fn unordered_maps() {
+        let a: HashMap<String, u32> = (0..32).map(|i| (i.to_string(), i)).collect();
+        let b: HashMap<String, u32> = (0..32).rev().map(|i| (i.to_string(), i)).collect();
+
+        assert_eq!(digest(&a).unwrap(), digest(&b).unwrap());
+    }
+
+    #[test]
+    fn ordered_lists() {
+        assert_ne!(
+            digest(&vec!["a", "b"]).unwrap(),
+            digest(&vec!["b", "a"]).unwrap()
+        );
+    }
+
+    #[test]
+    fn distinguishes_content() {
+        assert_ne!(digest(&1u32).unwrap(), digest(&1u64).unwrap());
+        assert_ne!(digest(&"ab").unwrap(), digest(&"ba").unwrap());
+        assert_ne!(
+            digest(&vec!["ab", "c"]).unwrap(),
+            digest(&vec!["a", "bc"]).unwrap()
+        );
+        assert_ne!(digest(&true).unwrap(), digest(&false).unwrap());
+    }
+
+    #[test]
+    fn ignores_id() {
+        let fixed = "foo".into_unique_with(|| ID::from_name("foo"));
+
+        assert_eq!(digest(&fixed).unwrap(), digest(&"foo").unwrap());
+    }
+
+    #[test]
+    fn too_deep() {
+        struct Deep(usize);
+
+        impl Data for Deep {
+            fn provide_links(&self, links: &mut dyn Links) -> core::result::Result<(), LinkError> {
+                if self.0 > 0 {
+                    links.push_link(Deep(self.0 - 1))?;
+                }
+                Ok(())
+            }
+        }
+
+        assert!(digest(&Deep(MAX_DEPTH)).is_ok());
+        assert!(digest(&Deep(MAX_DEPTH + 1)).is_err());
+    }
+
+    #[test]
+    fn shared_data() {
+        /// Links twice to the layer below, so hashing without memoization is exponential
+        struct Layer(u32);
+
+        impl Data for Layer {
+            fn provide_links(&self, links: &mut dyn Links) -> core::result::Result<(), LinkError> {
+                if self.0 > 0 {
+                    let id = ID::from_name(&(self.0 - 1).to_string());
+                    links.push_link(Layer(self.0 - 1).into_unique(id))?;
+                    links.push_link(Layer(self.0 - 1).into_unique(id))?;
+                }
+                Ok(())
+            }
+        }
+
+        assert!(digest(&Layer(100)).is_ok());
+    }
+
+    #[test]
+    fn cyclic() {
+        struct Loop;
+
+        impl Data for Loop {
+            fn provide_links(&self, links: &mut dyn Links) -> core::result::Result<(), LinkError> {
+                links.push_link(Loop.into_unique(ID::from_name("loop")))?;
+                Ok(())
+            }
+        }
+
+        let err = digest(&Loop.into_unique(ID::from_name("loop")));
+        assert!(err.is_err_and(|e| e.to_string().contains("links back")));
+    }
+
+    #[test]
+    fn unnamed_other_values() {
+        struct Opaque;
+
+        impl Data for Opaque {
+            fn provide_value(&self, request: &mut Request) {
+                request.provide_ref(self);
+            }
+        }
+
+        assert_ne!(digest(&Opaque).unwrap(), digest(&()).unwrap());
+    }
+
+    #[test]
+    fn wrapper() {
+        let data = HashMap::from([("a", 1)]);
+        let wrapped = ContentAddressed::new(data.clone()).unwrap();
+
+        assert_eq!(wrapped.get_id(), Some(content_id(&data).unwrap()));
+        assert_eq!(wrapped.as_ref(), &data);
+    }
+}
@@ -283,15 +283,143 @@ pub trait DataExt: Data {
         request.take().0
     }
 
+    /// Get a number as `T`, converting it from other numeric types if that's lossless
+    ///
+    /// ```rust
+    /// use datalink::data::DataExt;
+    ///
+    /// assert_eq!(42i32.as_lossless::<u64>(), Some(42));
+    /// assert_eq!((-1i32).as_lossless::<u64>(), None);
+    /// assert_eq!(2.0f64.as_lossless::<u8>(), Some(2));
+    /// assert_eq!(2.5f64.as_lossless::<u8>(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    fn as_lossless<T: crate::rr::lossless::Numeric>(&self) -> Option<T>
+    where
+        Self: Sized,
+    {
+        use crate::rr::lossless::Lossless;
+        let mut request = Request::<Lossless<T>>::default();
+        if !self.provide_requested(&mut request).was_provided() {
+            self.provide_value(&mut request.as_erased());
+        }
+
+        request.take().into_inner()
+    }
+
     #[inline]
     fn query<L: Links + Default>(&self, query: &Query) -> Result<L, LinkError> {
         let mut links = L::default();
 
-        self.query_links(&mut links, query)?;
+        query.run(self, &mut links)?;
 
         Ok(links)
     }
 
+    /// Iterates the links, lazily if the data supports [`Data::iter_links`]
+    ///
+    /// ```rust
+    /// use datalink::data::DataExt;
+    ///
+    /// let v = vec![1u8, 2, 3];
+    /// let mut links = v.links().unwrap().peekable();
+    /// assert!(links.peek().is_some_and(|(key, _)| key.is_none()));
+    /// assert_eq!(links.count(), 3);
+    /// ```
+    ///
+    /// # Errors
+    /// Any error returned by [`Data::provide_links`] if the links are collected.
+    #[inline]
+    fn links(&self) -> Result<crate::links::iter::LinkIter<'_>, LinkError> {
+        use crate::links::iter::LinkIter;
+        match self.iter_links() {
+            Some(links) => Ok(links),
+            None => LinkIter::buffered(self),
+        }
+    }
+
+    /// Iterates the links matching the query
+    ///
+    /// Filters the data evaluates itself, like key lookups in maps, are pushed down and the
+    /// matching links collected, others are applied to the lazy iterator if there is one.
+    ///
+    /// ```rust
+    /// use datalink::data::DataExt;
+    /// use datalink::query::{DataFilter, Query};
+    ///
+    /// let v = vec![1u8, 5, 2, 7];
+    /// let query = Query::from(DataFilter::gt(4u8));
+    /// let big = v.query_iter(&query).unwrap();
+    /// assert_eq!(big.filter_map(|(_, t)| t.as_u8()).collect::<Vec<_>>(), [5, 7]);
+    /// ```
+    ///
+    /// # Errors
+    /// Any error returned by [`Data::query_links`] if the links are collected.
+    #[inline]
+    fn query_iter<'a>(
+        &'a self,
+        query: &'a Query,
+    ) -> Result<crate::links::iter::LinkIter<'a>, LinkError> {
+        use crate::links::iter::LinkIter;
+        use crate::query::LinkFilter;
+
+        let filter = query.filter();
+        let pushed = !matches!(filter, LinkFilter::Any) && self.supports_filter(filter);
+        match self.iter_links() {
+            Some(links) if !pushed => Ok(links.matching(query)),
+            _ => LinkIter::buffered_query(self, query),
+        }
+    }
+
+    /// Pushes all links matching the query into an aggregate and returns its result
+    ///
+    /// ```rust
+    /// use datalink::data::DataExt;
+    /// use datalink::links::aggregate::Max;
+    /// use datalink::query::{DataFilter, Query};
+    ///
+    /// let v = vec![3u8, 7, 12];
+    /// let max = v.aggregate(&Query::from(DataFilter::lt(10u8)), Max::default());
+    /// assert_eq!(max.unwrap(), Some(7u8.into()));
+    /// ```
+    #[inline]
+    fn aggregate<A: crate::links::aggregate::Aggregate>(
+        &self,
+        query: &Query,
+        mut agg: A,
+    ) -> Result<A::Output, LinkError> {
+        query.run(self, &mut agg)?;
+
+        Ok(agg.finish())
+    }
+
+    /// Groups all targets by the first value at a key path, see [`GroupBy`](crate::links::group::GroupBy)
+    ///
+    /// ```rust
+    /// use datalink::data::DataExt;
+    /// use datalink::value::Value;
+    /// use std::collections::HashMap;
+    ///
+    /// let logs = vec![
+    ///     HashMap::from([("level", "info")]),
+    ///     HashMap::from([("level", "info")]),
+    /// ];
+    ///
+    /// let groups = logs.group_by(&["level"]).unwrap();
+    /// assert_eq!(groups.get(&Value::String("info".into())).map(Vec::len), Some(2));
+    /// ```
+    #[inline]
+    fn group_by(
+        &self,
+        path: &[&str],
+    ) -> Result<crate::links::group::Groups<Vec<std::sync::Arc<dyn Data>>>, LinkError> {
+        use crate::links::group::GroupBy;
+        const ALL: Query = Query::new(crate::query::LinkFilter::Any);
+
+        self.aggregate(&ALL, GroupBy::new(path))
+    }
+
     /// Collects all links without a key into a vec.
     ///
     /// Note:
@@ -398,6 +526,38 @@ pub trait DataExt: Data {
         Ok(self.query::<Linked>(&query)? == Linked::Yes)
     }
 
+    /// Get the target of the first link with `key` which is of the key's target type
+    ///
+    /// ```rust
+    /// use std::net::SocketAddr;
+    /// use datalink::data::DataExt;
+    /// use datalink::well_known::net::PORT;
+    ///
+    /// let addr: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+    ///
+    /// assert_eq!(addr.get_key(&PORT), Some(8080));
+    /// ```
+    #[cfg(feature = "unique")]
+    #[inline]
+    #[must_use]
+    fn get_key<const I: u128, T, K: ?Sized>(
+        &self,
+        key: &crate::data::key::Key<I, T, K>,
+    ) -> Option<T>
+    where
+        for<'d> &'d K: Data,
+        Option<T>: Receiver + 'static,
+    {
+        use crate::data::unique::Unique;
+        let query = {
+            use crate::query::prelude::*;
+            Query::new(Link::Key(Data::Id(key.id())))
+        };
+
+        let targets: Vec<crate::data::BoxedData> = self.query(&query).ok()?;
+        targets.into_iter().find_map(|target| target.as_())
+    }
+
     #[inline]
     #[must_use]
     fn all_values(&self) -> crate::value::AllValues
@@ -429,6 +589,21 @@ pub trait DataExt: Data {
     fn format<F: format::Format>(&self) -> format::FormattableData<F, Self> {
         self.into()
     }
+
+    /// Renders the data as an indented tree for reading in a terminal
+    ///
+    /// ```rust
+    /// use datalink::data::DataExt;
+    /// use std::io::IsTerminal;
+    ///
+    /// let data = vec!["foo"];
+    /// println!("{}", data.tree().with_color(std::io::stdout().is_terminal()));
+    /// ```
+    #[inline]
+    #[must_use]
+    fn tree(&self) -> format::Tree<'_, Self> {
+        format::Tree::new(self)
+    }
 }
 
 impl<T: Data + ?Sized> DataExt for T {}
@@ -11,12 +11,23 @@ use crate::{
 
 use super::DataExt;
 
+pub mod tree;
+pub use tree::Tree;
+
+/// Configurable format
+///
+/// `ID_ENCODING` selects how IDs are shown, see [`Encoding::from_u8`](crate::id::Encoding::from_u8).
 #[derive(Default, Debug)]
-pub struct FORMAT<const SERIAL: bool = false, const MAX_DEPTH: u16 = 6, const VERBOSITY: i8 = 0>;
+pub struct FORMAT<
+    const SERIAL: bool = false,
+    const MAX_DEPTH: u16 = 6,
+    const VERBOSITY: i8 = 0,
+    const ID_ENCODING: u8 = 0,
+>;
 
 pub type COMPACT<const MAX_DEPTH: u16 = 6, const VERBOSITY: i8 = -1> =
     FORMAT<true, MAX_DEPTH, VERBOSITY>;
-pub type DEBUG = FORMAT<true, 6, 1>;
+pub type DEBUG<const ID_ENCODING: u8 = 0> = FORMAT<true, 6, 1, ID_ENCODING>;
 
 trait Verbosity {
     fn collapse_value(&self) -> bool;
@@ -204,8 +215,8 @@ pub trait Format {
     }
 }
 
-impl<const SERIAL: bool, const MAX_DEPTH: u16, const VERBOSITY: i8> Format
-    for FORMAT<SERIAL, MAX_DEPTH, VERBOSITY>
+impl<const SERIAL: bool, const MAX_DEPTH: u16, const VERBOSITY: i8, const ID_ENCODING: u8> Format
+    for FORMAT<SERIAL, MAX_DEPTH, VERBOSITY, ID_ENCODING>
 {
     type State = u16;
     const ELLIPSIS_THRESHOLD: usize = {
@@ -237,6 +248,11 @@ impl<const SERIAL: bool, const MAX_DEPTH: u16, const VERBOSITY: i8> Format
         #[cfg(feature = "unique")]
         if VERBOSITY.show_id() {
             if let Some(id) = data.get_id() {
+                #[cfg(feature = "well_known")]
+                if let Some(term) = crate::well_known::registry::lookup(id) {
+                    return f.write_fmt(format_args!("[{term}]"));
+                }
+                let id = id.encode(crate::id::Encoding::from_u8(ID_ENCODING));
                 f.write_fmt(format_args!("[{id}]"))?;
             }
         }
@@ -647,6 +663,30 @@ mod tests {
         assert_ne!(debug_unkeyed, debug_keyed);
     }
 
+    #[test]
+    #[cfg(feature = "unique")]
+    fn id_encoding() {
+        use crate::id::Encoding;
+        struct Foo;
+        crate::impl_data!(Foo, id = 0x734BFA09_662B_477C_8B61_7E85B6C40000);
+
+        let decimal = Foo.format::<DEBUG>().to_string();
+        let uuid = Foo.format::<DEBUG<{ Encoding::Uuid as u8 }>>().to_string();
+        let hex = Foo.format::<DEBUG<{ Encoding::Hex as u8 }>>().to_string();
+
+        assert!(decimal.contains(&format!("[{}]", 0x734BFA09_662B_477C_8B61_7E85B6C40000u128)));
+        assert!(uuid.contains("[734bfa09-662b-477c-8b61-7e85b6c40000]"));
+        assert!(hex.contains("[0x734bfa09662b477c8b617e85b6c40000]"));
+    }
+
+    #[test]
+    #[cfg(feature = "well_known")]
+    fn well_known_id() {
+        let tag = crate::well_known::TAG;
+
+        assert!(tag.format::<DEBUG>().to_string().contains("[TAG]"));
+    }
+
     #[test]
     #[ignore]
     #[cfg(feature = "std")]
@@ -0,0 +1,437 @@
+use std::fmt::{self, Debug, Display, Write};
+
+use crate::{
+    data::{BoxedData, Data},
+    links::{Link, MaybeKeyed},
+    rr::{meta::MetaInfo, Request},
+    value::{AllValues, Value},
+};
+
+#[cfg(feature = "unique")]
+use crate::id::Encoding;
+
+const BRANCH: &str = "├─ ";
+const LAST_BRANCH: &str = "└─ ";
+const GUIDE: &str = "│  ";
+const SPACE: &str = "   ";
+
+/// Renders data as an indented tree in the style of `tree`
+///
+/// Colour is off unless enabled with [`with_color`](Self::with_color), e.g. if stdout is a terminal.
+/// The terminal width is read from `COLUMNS` and defaults to 80, wide characters count as two columns.
+/// IDs are shown in their UUID form unless configured otherwise, registered well-known terms by their name.
+///
+/// ```rust
+/// use datalink::data::format::Tree;
+///
+/// let data = vec![1u8, 2u8];
+/// let tree = Tree::new(&data).to_string();
+///
+/// assert_eq!(tree, "Data\n├─ 1u8\n└─ 2u8\n");
+/// ```
+pub struct Tree<'d, D: Data + ?Sized> {
+    data: &'d D,
+    color: bool,
+    width: usize,
+    max_depth: u16,
+    #[cfg(feature = "unique")]
+    id_encoding: Encoding,
+}
+
+impl<'d, D: Data + ?Sized> Tree<'d, D> {
+    #[inline]
+    #[must_use]
+    pub fn new(data: &'d D) -> Self {
+        Self {
+            data,
+            color: false,
+            width: detect_width(),
+            max_depth: 6,
+            #[cfg(feature = "unique")]
+            id_encoding: Encoding::Uuid,
+        }
+    }
+
+    /// Enable colour, which is off by default
+    #[inline]
+    #[must_use]
+    pub const fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Width to wrap lines at. `0` disables wrapping.
+    #[inline]
+    #[must_use]
+    pub const fn with_width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn with_max_depth(mut self, max_depth: u16) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    #[cfg(feature = "unique")]
+    #[inline]
+    #[must_use]
+    pub const fn with_id_encoding(mut self, encoding: Encoding) -> Self {
+        self.id_encoding = encoding;
+        self
+    }
+}
+
+impl<D: Data + ?Sized> Display for Tree<'_, D> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut painter = Painter {
+            f,
+            color: self.color,
+            width: self.width,
+            column: 0,
+            #[cfg(feature = "unique")]
+            id_encoding: self.id_encoding,
+        };
+
+        let label = painter.label(self.data, Style::Value);
+        if label.is_empty() {
+            painter.paint(Style::Guide, "Data", "")?;
+        }
+        painter.paint_all(&label, "")?;
+        painter.newline()?;
+
+        fmt_children(&mut painter, self.data, "", self.max_depth)
+    }
+}
+
+impl<D: Data + ?Sized> Debug for Tree<'_, D> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+fn detect_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse().ok())
+        .unwrap_or(80)
+}
+
+fn fmt_children(
+    p: &mut Painter,
+    data: &(impl Data + ?Sized),
+    prefix: &str,
+    depth: u16,
+) -> fmt::Result {
+    let mut links = Vec::<MaybeKeyed<BoxedData, BoxedData>>::new();
+    // Ignore errors
+    let _ = data.provide_links(&mut links);
+
+    if links.is_empty() {
+        return Ok(());
+    }
+
+    if depth == 0 {
+        p.paint(Style::Guide, prefix, "")?;
+        p.paint(Style::Guide, LAST_BRANCH, "")?;
+        p.paint(Style::Guide, "…", "")?;
+        return p.newline();
+    }
+
+    let count = links.len();
+    for (i, link) in links.into_iter().enumerate() {
+        let last = i + 1 == count;
+        let inner = format!("{prefix}{}", if last { SPACE } else { GUIDE });
+        // Continuation lines of wrapped labels are indented below the branch
+        let cont = format!("{inner}  ");
+
+        p.paint(Style::Guide, prefix, "")?;
+        p.paint(Style::Guide, if last { LAST_BRANCH } else { BRANCH }, "")?;
+
+        let target = link.target();
+        let target_label = p.label(target, Style::Value);
+        if let Some(key) = link.key() {
+            let key_label = p.label(key, Style::Key);
+            if key_label.is_empty() {
+                p.paint(Style::Key, "?", &cont)?;
+            }
+            p.paint_all(&key_label, &cont)?;
+            if !target_label.is_empty() {
+                p.paint(Style::Guide, ": ", &cont)?;
+            }
+        }
+        p.paint_all(&target_label, &cont)?;
+        p.newline()?;
+
+        fmt_children(p, target, &inner, depth - 1)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Guide,
+    Key,
+    Value,
+    #[cfg(feature = "unique")]
+    Id,
+    Meta,
+}
+
+impl Style {
+    const RESET: &'static str = "\x1b[0m";
+
+    const fn code(self) -> &'static str {
+        match self {
+            Style::Guide => "\x1b[2m",
+            Style::Key => "\x1b[1;34m",
+            Style::Value => "\x1b[32m",
+            #[cfg(feature = "unique")]
+            Style::Id => "\x1b[35m",
+            Style::Meta => "\x1b[36m",
+        }
+    }
+}
+
+/// Writes styled text and wraps it at `width`
+struct Painter<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    color: bool,
+    width: usize,
+    column: usize,
+    #[cfg(feature = "unique")]
+    id_encoding: Encoding,
+}
+
+impl Painter<'_, '_> {
+    /// Styled segments showing the values and the ID of `data`
+    fn label(&self, data: &(impl Data + ?Sized), style: Style) -> Vec<(Style, String)> {
+        let mut request = Request::<AllValues>::default();
+        data.provide_value(&mut request.as_erased());
+
+        let mut segments = Vec::new();
+        for value in request.take() {
+            if !segments.is_empty() {
+                segments.push((Style::Guide, ", ".to_owned()));
+            }
+            let segment = match value {
+                Value::Other(val) => match MetaInfo::about_val(val.as_ref()).name() {
+                    Some(name) => (Style::Meta, format!("#{name}")),
+                    None => (Style::Meta, "{unknown}".to_owned()),
+                },
+                val => (style, val.to_string()),
+            };
+            segments.push(segment);
+        }
+
+        #[cfg(feature = "unique")]
+        if let Some(id) = data.get_id() {
+            if !segments.is_empty() {
+                segments.push((Style::Guide, " ".to_owned()));
+            }
+            #[cfg(feature = "well_known")]
+            if let Some(term) = crate::well_known::registry::lookup(id) {
+                segments.push((Style::Id, format!("[{term}]")));
+                return segments;
+            }
+            let id = id.encode(self.id_encoding);
+            segments.push((Style::Id, format!("[{id}]")));
+        }
+
+        segments
+    }
+
+    fn paint(&mut self, style: Style, text: &str, cont: &str) -> fmt::Result {
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        let cont_len = cont.chars().map(char_width).sum();
+        // Wrapping is impossible if the continuation prefix already fills the line
+        let wrap = self.width > cont_len + 1;
+
+        self.start(style)?;
+        for c in text.chars() {
+            let width = char_width(c);
+            if wrap && self.column + width > self.width && self.column > cont_len {
+                self.end()?;
+                self.f.write_char('\n')?;
+                self.start(Style::Guide)?;
+                self.f.write_str(cont)?;
+                self.end()?;
+                self.column = cont_len;
+                self.start(style)?;
+            }
+            self.f.write_char(c)?;
+            self.column += width;
+        }
+        self.end()
+    }
+
+    fn paint_all(&mut self, segments: &[(Style, String)], cont: &str) -> fmt::Result {
+        segments
+            .iter()
+            .try_for_each(|(style, text)| self.paint(*style, text, cont))
+    }
+
+    fn newline(&mut self) -> fmt::Result {
+        self.column = 0;
+        self.f.write_char('\n')
+    }
+
+    fn start(&mut self, style: Style) -> fmt::Result {
+        if self.color {
+            self.f.write_str(style.code())?;
+        }
+        Ok(())
+    }
+
+    fn end(&mut self) -> fmt::Result {
+        if self.color {
+            self.f.write_str(Style::RESET)?;
+        }
+        Ok(())
+    }
+}
+
+/// Columns a character takes up in a terminal
+///
+/// Covers combining marks and the common wide ranges of CJK and emoji,
+/// not the full East Asian Width tables.
+fn char_width(c: char) -> usize {
+    match u32::from(c) {
+        0x00..=0x1F
+        | 0x7F..=0x9F
+        | 0x0300..=0x036F
+        | 0x1AB0..=0x1AFF
+        | 0x1DC0..=0x1DFF
+        | 0x200B..=0x200F
+        | 0x20D0..=0x20FF
+        | 0xFE00..=0xFE0F
+        | 0xFE20..=0xFE2F
+        | 0xE0100..=0xE01EF => 0,
+        0x1100..=0x115F
+        | 0x231A..=0x231B
+        | 0x23E9..=0x23EC
+        | 0x2614..=0x2615
+        | 0x2648..=0x2653
+        | 0x26AA..=0x26AB
+        | 0x26BD..=0x26BE
+        | 0x2705
+        | 0x270A..=0x270B
+        | 0x2728
+        | 0x274C
+        | 0x2753..=0x2757
+        | 0x2B50
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xA960..=0xA97F
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F004
+        | 0x1F0CF
+        | 0x1F18E
+        | 0x1F191..=0x1F19A
+        | 0x1F200..=0x1F251
+        | 0x1F300..=0x1F64F
+        | 0x1F680..=0x1F6FF
+        | 0x1F7E0..=0x1F7EB
+        | 0x1F900..=0x1F9FF
+        | 0x1FA70..=0x1FAFF
+        | 0x20000..=0x2FFFD
+        | 0x30000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::links::{Links, LinksExt, Result};
+
+    struct Nested;
+
+    impl Data for Nested {
+        fn provide_links(&self, links: &mut dyn Links) -> Result<()> {
+            links.push_link(("list", vec!["a", "b"]))?;
+            links.push_link(("flag", true))?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn leaf() {
+        let tree = Tree::new(&"foo").with_color(false).to_string();
+
+        assert_eq!(tree, "\"foo\"\n");
+    }
+
+    #[test]
+    fn nested() {
+        let tree = Tree::new(&Nested).with_color(false).to_string();
+
+        assert_eq!(
+            tree,
+            "Data\n├─ \"list\"\n│  ├─ \"a\"\n│  └─ \"b\"\n└─ \"flag\": true\n"
+        );
+    }
+
+    #[test]
+    fn max_depth() {
+        let tree = Tree::new(&Nested)
+            .with_color(false)
+            .with_max_depth(1)
+            .to_string();
+
+        assert_eq!(tree, "Data\n├─ \"list\"\n│  └─ …\n└─ \"flag\": true\n");
+    }
+
+    #[test]
+    fn wraps_long_strings() {
+        let data = vec!["abcdefghij"];
+        let tree = Tree::new(&data)
+            .with_color(false)
+            .with_width(10)
+            .to_string();
+
+        assert_eq!(tree, "Data\n└─ \"abcdef\n     ghij\"\n");
+    }
+
+    #[test]
+    fn wraps_wide_characters() {
+        let data = vec!["日本語のテキスト"];
+        let tree = Tree::new(&data).with_width(12).to_string();
+
+        assert_eq!(tree, "Data\n└─ \"日本語の\n     テキス\n     ト\"\n");
+        assert_eq!(char_width('e'), 1);
+        assert_eq!(char_width('\u{301}'), 0);
+        assert_eq!(char_width('🦀'), 2);
+    }
+
+    #[test]
+    fn plain_by_default() {
+        let tree = Tree::new(&Nested).to_string();
+
+        assert!(!tree.contains(Style::RESET));
+    }
+
+    #[test]
+    fn colored() {
+        let tree = Tree::new(&Nested).with_color(true).to_string();
+
+        assert!(tree.contains(Style::Key.code()));
+        assert!(tree.contains(Style::Value.code()));
+        assert!(tree.contains(Style::RESET));
+    }
+}
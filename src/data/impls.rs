@@ -7,6 +7,33 @@ mod json;
 mod std;
 #[cfg(feature = "toml")]
 mod toml;
+#[cfg(feature = "uuid")]
+mod uuid;
+
+/// Keys of links from std types, the well known terms if enabled
+#[cfg(all(feature = "std", feature = "well_known"))]
+use crate::well_known as keys;
+
+/// Keys of links from std types, with the same names as the well known terms
+#[cfg(all(feature = "std", not(feature = "well_known")))]
+mod keys {
+    pub(super) const CREATED_AT: &str = "created_at";
+    pub(super) const MODIFIED_AT: &str = "modified_at";
+    pub(super) const NAME: &str = "name";
+    pub(super) const PARENT: &str = "parent";
+    pub(super) const TYPE: &str = "type";
+    pub(super) const UNIT: &str = "unit";
+
+    pub(super) mod fs {
+        pub(in crate::data::impls) const PATH: &str = "path";
+        pub(in crate::data::impls) const SIZE: &str = "size";
+    }
+
+    pub(super) mod net {
+        pub(in crate::data::impls) const IP: &str = "ip";
+        pub(in crate::data::impls) const PORT: &str = "port";
+    }
+}
 
 #[macro_export]
 macro_rules! impl_deref {
@@ -39,6 +66,14 @@ macro_rules! impl_deref {
                 (**self).query_links(links, query)
             }
             #[inline]
+            fn supports_filter(&self, filter: &$crate::query::LinkFilter) -> bool {
+                (**self).supports_filter(filter)
+            }
+            #[inline]
+            fn iter_links(&self) -> Option<$crate::links::iter::LinkIter<'_>> {
+                (**self).iter_links()
+            }
+            #[inline]
             fn get_id(&self) -> Option<$crate::id::ID> {
                 (**self).get_id()
             }
@@ -88,6 +123,14 @@ macro_rules! impl_dyn {
                 (**self).query_links(links, query)
             }
             #[inline]
+            fn supports_filter(&self, filter: &crate::query::LinkFilter) -> bool {
+                (**self).supports_filter(filter)
+            }
+            #[inline]
+            fn iter_links(&self) -> Option<crate::links::iter::LinkIter<'_>> {
+                (**self).iter_links()
+            }
+            #[inline]
             fn get_id(&self) -> Option<crate::id::ID> {
                 (**self).get_id()
             }
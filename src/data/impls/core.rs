@@ -217,6 +217,16 @@ impl<D: Data> Data for Option<D> {
         Ok(())
     }
 
+    #[inline]
+    fn supports_filter(&self, filter: &crate::query::LinkFilter) -> bool {
+        self.as_ref().is_none_or(|d| d.supports_filter(filter))
+    }
+
+    #[inline]
+    fn iter_links(&self) -> Option<crate::links::iter::LinkIter<'_>> {
+        self.as_ref().and_then(Data::iter_links)
+    }
+
     #[inline]
     fn get_id(&self) -> Option<crate::id::ID> {
         self.as_ref().and_then(Data::get_id)
@@ -274,6 +284,22 @@ where
         }
     }
 
+    #[inline]
+    fn supports_filter(&self, filter: &crate::query::LinkFilter) -> bool {
+        match self {
+            Self::Borrowed(data) => data.supports_filter(filter),
+            Self::Owned(data) => data.supports_filter(filter),
+        }
+    }
+
+    #[inline]
+    fn iter_links(&self) -> Option<crate::links::iter::LinkIter<'_>> {
+        match self {
+            Self::Borrowed(data) => data.iter_links(),
+            Self::Owned(data) => data.iter_links(),
+        }
+    }
+
     #[inline]
     fn get_id(&self) -> Option<crate::id::ID> {
         match self {
@@ -322,6 +348,14 @@ impl<D: Data> Data for core::cell::OnceCell<D> {
     ) -> Result<(), LinkError> {
         self.get().query_links(links, query)
     }
+    #[inline]
+    fn supports_filter(&self, filter: &crate::query::LinkFilter) -> bool {
+        self.get().supports_filter(filter)
+    }
+    #[inline]
+    fn iter_links(&self) -> Option<crate::links::iter::LinkIter<'_>> {
+        self.get().and_then(Data::iter_links)
+    }
 
     #[inline]
     fn get_id(&self) -> Option<crate::id::ID> {
@@ -350,6 +384,14 @@ impl<D: Data> Data for std::sync::OnceLock<D> {
     ) -> Result<(), LinkError> {
         self.get().query_links(links, query)
     }
+    #[inline]
+    fn supports_filter(&self, filter: &crate::query::LinkFilter) -> bool {
+        self.get().supports_filter(filter)
+    }
+    #[inline]
+    fn iter_links(&self) -> Option<crate::links::iter::LinkIter<'_>> {
+        self.get().and_then(Data::iter_links)
+    }
 
     #[inline]
     fn get_id(&self) -> Option<crate::id::ID> {
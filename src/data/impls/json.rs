@@ -1,7 +1,8 @@
 use serde_json::{Map, Number, Value as Val};
 
 use crate::data::{Data, Provided};
-use crate::links::{LinkError, Links, LinksExt};
+use crate::links::{iter::LinkIter, LinkError, Links, LinksExt};
+use crate::query::{KeyHint, LinkFilter};
 use crate::rr::{meta, Query, Request};
 
 impl Data for Val {
@@ -32,6 +33,37 @@ impl Data for Val {
         }
     }
 
+    #[inline]
+    fn query_links(
+        &self,
+        links: &mut dyn Links,
+        query: &crate::query::Query,
+    ) -> Result<(), LinkError> {
+        match self {
+            Val::Array(v) => v.query_links(links, query),
+            Val::Object(m) => m.query_links(links, query),
+            _ => Ok(()),
+        }
+    }
+
+    #[inline]
+    fn supports_filter(&self, filter: &LinkFilter) -> bool {
+        match self {
+            Val::Array(v) => v.supports_filter(filter),
+            Val::Object(m) => m.supports_filter(filter),
+            _ => true,
+        }
+    }
+
+    #[inline]
+    fn iter_links(&self) -> Option<LinkIter<'_>> {
+        match self {
+            Val::Array(v) => v.iter_links(),
+            Val::Object(m) => m.iter_links(),
+            _ => Some(LinkIter::from_items(std::iter::empty())),
+        }
+    }
+
     #[inline]
     fn get_id(&self) -> Option<crate::id::ID> {
         match self {
@@ -55,16 +87,34 @@ impl Data for Map<String, Val> {
         links: &mut dyn Links,
         query: &crate::query::Query,
     ) -> Result<(), LinkError> {
-        use crate::query::Filter;
-        links.extend(self.iter().filter_map(|(k, v)| {
-            if query.matches_owned((k, v)) {
-                Some((k.to_owned(), v.to_owned()))
-            } else {
-                None
-            }
-        }))?;
+        let matching = |(k, v): (&String, &Val)| {
+            query
+                .matches_typed(&(k, v))
+                .then(|| (k.to_owned(), v.to_owned()))
+        };
+        if let Some(KeyHint::Exact(keys)) = query.filter().key_hint() {
+            let entries = keys.into_iter().filter_map(|k| self.get_key_value(k));
+            links.extend(entries.filter_map(matching))?;
+        } else {
+            links.extend(self.iter().filter_map(matching))?;
+        }
         Ok(())
     }
+
+    #[inline]
+    fn supports_filter(&self, filter: &LinkFilter) -> bool {
+        match filter {
+            LinkFilter::Any | LinkFilter::None => true,
+            filter => filter.key_hint().is_some_and(|h| h.is_exact()),
+        }
+    }
+
+    #[inline]
+    fn iter_links(&self) -> Option<LinkIter<'_>> {
+        Some(LinkIter::new(
+            self.iter().map(|(k, v)| (k.to_owned(), v.to_owned())),
+        ))
+    }
 }
 
 impl Data for Number {
@@ -108,4 +158,21 @@ mod tests {
         n.provide_requested(&mut r).assert_provided();
         assert_eq!(r.take(), Some(42));
     }
+
+    #[test]
+    fn key_lookup() {
+        use crate::data::{BoxedData, DataExt};
+        use crate::query::{DataFilter, LinkFilter, Query};
+
+        let value = serde_json::json!({"a": 1, "b": 2});
+        let query = Query::new(LinkFilter::key(DataFilter::exact("b")));
+        assert!(value.supports_filter(query.filter()));
+        let links: Vec<(BoxedData, BoxedData)> = value.query(&query).unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].1.as_u64(), Some(2));
+
+        let query = Query::new(LinkFilter::Any).with_limit(1);
+        let links: Vec<BoxedData> = value.query(&query).unwrap();
+        assert_eq!(links.len(), 1);
+    }
 }
@@ -1,7 +1,12 @@
-use ::std::collections::HashMap;
+use ::std::any::Any;
+use ::std::borrow::Borrow;
+use ::std::collections::{BTreeMap, HashMap};
+use ::std::hash::{BuildHasher, Hash};
+use ::std::ops::Bound;
 
 use crate::data::{Data, Provided};
-use crate::links::{LinkError, Links, LinksExt};
+use crate::links::{iter::LinkIter, LinkError, Links, LinksExt};
+use crate::query::{is_empty_range, KeyHint, LinkFilter};
 use crate::rr::{Query, Request};
 
 impl Data for String {
@@ -21,6 +26,24 @@ mod path {
     use ::std::os::unix::ffi::OsStrExt;
     use ::std::path::{Path, PathBuf};
 
+    use crate::data::impls::keys::{NAME, PARENT};
+
+    /// Links the file name and the parent of a path
+    ///
+    /// The parent is linked as an `OsString` rather than a path,
+    /// so it's a leaf instead of linking all ancestors up to the root.
+    fn provide_path_links(path: &Path, links: &mut dyn Links) -> Result<(), LinkError> {
+        if let Some(name) = path.file_name() {
+            links.push_keyed(Box::new(name.to_owned()), Box::new(NAME))?;
+        }
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            let parent = parent.as_os_str().to_owned();
+            links.push_keyed(Box::new(parent), Box::new(PARENT))?;
+        }
+
+        Ok(())
+    }
+
     impl Data for PathBuf {
         #[inline]
         fn provide_value(&self, request: &mut Request) {
@@ -38,6 +61,11 @@ mod path {
             #[cfg(target_os = "linux")]
             request.provide_bytes(OsStrExt::as_bytes(self.as_os_str()));
         }
+
+        #[inline]
+        fn provide_links(&self, links: &mut dyn Links) -> Result<(), LinkError> {
+            provide_path_links(self, links)
+        }
     }
 
     impl Data for Path {
@@ -57,6 +85,125 @@ mod path {
             #[cfg(target_os = "linux")]
             request.provide_bytes(OsStrExt::as_bytes(self.as_os_str()));
         }
+
+        #[inline]
+        fn provide_links(&self, links: &mut dyn Links) -> Result<(), LinkError> {
+            provide_path_links(self, links)
+        }
+    }
+}
+
+mod fs {
+    use super::*;
+    use ::std::fs::{DirEntry, Metadata};
+
+    use crate::data::impls::keys::{
+        fs::{PATH, SIZE},
+        CREATED_AT, MODIFIED_AT, NAME, TYPE,
+    };
+
+    impl Data for Metadata {
+        #[inline]
+        fn provide_value(&self, request: &mut Request) {
+            self.provide_requested(request).debug_assert_provided();
+        }
+
+        #[inline]
+        fn provide_requested<Q: Query>(&self, request: &mut Request<Q>) -> impl Provided {
+            request.provide_ref(self);
+        }
+
+        #[inline]
+        fn provide_links(&self, links: &mut dyn Links) -> Result<(), LinkError> {
+            let file_type = if self.is_dir() {
+                "dir"
+            } else if self.is_symlink() {
+                "symlink"
+            } else {
+                "file"
+            };
+            links.push_keyed(Box::new(file_type), Box::new(TYPE))?;
+            links.push_keyed(Box::new(self.len()), Box::new(SIZE))?;
+            if let Ok(modified) = self.modified() {
+                links.push_keyed(Box::new(modified), Box::new(MODIFIED_AT))?;
+            }
+            if let Ok(created) = self.created() {
+                links.push_keyed(Box::new(created), Box::new(CREATED_AT))?;
+            }
+
+            Ok(())
+        }
+    }
+
+    impl Data for DirEntry {
+        #[inline]
+        fn provide_value(&self, request: &mut Request) {
+            self.provide_requested(request).debug_assert_provided();
+        }
+
+        #[inline]
+        fn provide_requested<Q: Query>(&self, request: &mut Request<Q>) -> impl Provided {
+            request.provide_ref(self);
+        }
+
+        #[inline]
+        fn provide_links(&self, links: &mut dyn Links) -> Result<(), LinkError> {
+            links.push_keyed(Box::new(self.path()), Box::new(PATH))?;
+            links.push_keyed(Box::new(self.file_name()), Box::new(NAME))?;
+
+            Ok(())
+        }
+    }
+}
+
+mod time {
+    use super::*;
+    use ::std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use crate::data::impls::keys::UNIT;
+
+    /// Durations are provided as seconds
+    impl Data for Duration {
+        #[inline]
+        fn provide_value(&self, request: &mut Request) {
+            self.provide_requested(request).debug_assert_provided();
+        }
+
+        #[inline]
+        fn provide_requested<Q: Query>(&self, request: &mut Request<Q>) -> impl Provided {
+            request.provide_ref(self);
+            request.provide_f64(self.as_secs_f64());
+        }
+
+        #[inline]
+        fn provide_links(&self, links: &mut dyn Links) -> Result<(), LinkError> {
+            links.push_keyed(Box::new("s"), Box::new(UNIT))?;
+            Ok(())
+        }
+    }
+
+    /// Points in time are provided as seconds since the unix epoch
+    impl Data for SystemTime {
+        #[inline]
+        fn provide_value(&self, request: &mut Request) {
+            self.provide_requested(request).debug_assert_provided();
+        }
+
+        #[inline]
+        fn provide_requested<Q: Query>(&self, request: &mut Request<Q>) -> impl Provided {
+            request.provide_ref(self);
+            let secs = match self.duration_since(UNIX_EPOCH) {
+                Ok(since) => since.as_secs_f64(),
+                Err(err) => -err.duration().as_secs_f64(),
+            };
+            request.provide_f64(secs);
+        }
+
+        #[inline]
+        fn provide_links(&self, links: &mut dyn Links) -> Result<(), LinkError> {
+            links.push_keyed(Box::new("s"), Box::new(UNIT))?;
+            Ok(())
+        }
     }
 }
 
@@ -109,13 +256,7 @@ mod net {
     use super::*;
     use ::std::net;
 
-    #[cfg(not(feature = "well_known"))]
-    const IP: &str = "ip";
-    #[cfg(not(feature = "well_known"))]
-    const PORT: &str = "port";
-
-    #[cfg(feature = "well_known")]
-    use crate::well_known::net::{IP, PORT};
+    use crate::data::impls::keys::net::{IP, PORT};
 
     impl Data for net::Ipv4Addr {
         #[inline]
@@ -155,6 +296,7 @@ mod net {
 
         #[inline]
         fn provide_requested<Q: Query>(&self, request: &mut Request<Q>) -> impl Provided {
+            request.provide_ref(self);
             match self {
                 Self::V4(ip) => ip.provide_requested(request).was_provided(),
                 Self::V6(ip) => ip.provide_requested(request).was_provided(),
@@ -170,6 +312,7 @@ mod net {
         }
     }
 
+    /// Links its address as an [`IpAddr`](net::IpAddr), the target type of the [`IP`] key, and its port
     impl Data for net::SocketAddrV4 {
         #[inline]
         fn provide_value(&self, request: &mut Request) {
@@ -187,13 +330,14 @@ mod net {
 
         #[inline]
         fn provide_links(&self, links: &mut dyn Links) -> Result<(), LinkError> {
-            links.push_keyed(Box::new(self.ip().to_owned()), Box::new(IP))?;
+            links.push_keyed(Box::new(net::IpAddr::from(*self.ip())), Box::new(IP))?;
             links.push_keyed(Box::new(self.port()), Box::new(PORT))?;
 
             Ok(())
         }
     }
 
+    /// Links its address as an [`IpAddr`](net::IpAddr), the target type of the [`IP`] key, and its port
     impl Data for net::SocketAddrV6 {
         #[inline]
         fn provide_value(&self, request: &mut Request) {
@@ -211,7 +355,7 @@ mod net {
 
         #[inline]
         fn provide_links(&self, links: &mut dyn Links) -> Result<(), LinkError> {
-            links.push_keyed(Box::new(self.ip().to_owned()), Box::new(IP))?;
+            links.push_keyed(Box::new(net::IpAddr::from(*self.ip())), Box::new(IP))?;
             links.push_keyed(Box::new(self.port()), Box::new(PORT))?;
 
             Ok(())
@@ -242,7 +386,77 @@ mod net {
     }
 }
 
-impl<K, V, S: ::std::hash::BuildHasher> Data for HashMap<K, V, S>
+/// Whether keys of type `K` can be looked up by `&str`
+fn str_keys<K: 'static>() -> bool {
+    crate::type_eq!(K, String) || crate::type_eq!(K, &'static str)
+}
+
+/// Turns entries of a map keyed by `Q` back into entries keyed by `K`, which is `Q`
+fn rekey<'m, Q: 'static, K: 'static, V: 'm>(
+    entries: impl Iterator<Item = (&'m Q, &'m V)> + 'm,
+) -> impl Iterator<Item = (&'m K, &'m V)> + 'm {
+    entries.filter_map(|(k, v)| Some(((k as &dyn Any).downcast_ref::<K>()?, v)))
+}
+
+type Entries<'m, K, V> = Box<dyn Iterator<Item = (&'m K, &'m V)> + 'm>;
+
+/// Entries of `map` with one of `keys`, if its keys can be looked up by `&str`
+fn hash_lookup<'m, K: 'static, V: 'static, S: BuildHasher + 'static>(
+    map: &'m HashMap<K, V, S>,
+    keys: Vec<&'m str>,
+) -> Option<Entries<'m, K, V>> {
+    fn get<'m, Q, K, V, S>(map: &'m HashMap<Q, V, S>, keys: Vec<&'m str>) -> Entries<'m, K, V>
+    where
+        Q: Borrow<str> + Hash + Eq + 'static,
+        K: 'static,
+        S: BuildHasher,
+    {
+        Box::new(rekey(keys.into_iter().filter_map(|k| map.get_key_value(k))))
+    }
+    let any: &dyn Any = map;
+    if let Some(map) = any.downcast_ref::<HashMap<String, V, S>>() {
+        Some(get(map, keys))
+    } else {
+        any.downcast_ref::<HashMap<&'static str, V, S>>()
+            .map(|map| get(map, keys))
+    }
+}
+
+/// Entries of `map` that may match `hint`, if its keys can be looked up by `&str`
+fn btree_lookup<'m, K: 'static, V: 'static>(
+    map: &'m BTreeMap<K, V>,
+    hint: KeyHint<'m>,
+) -> Option<Entries<'m, K, V>> {
+    fn get<'m, Q, K, V>(map: &'m BTreeMap<Q, V>, hint: KeyHint<'m>) -> Entries<'m, K, V>
+    where
+        Q: Borrow<str> + Ord + 'static,
+        K: 'static,
+    {
+        match hint {
+            KeyHint::Exact(keys) => {
+                Box::new(rekey(keys.into_iter().filter_map(|k| map.get_key_value(k))))
+            }
+            KeyHint::Prefix(prefix) => Box::new(rekey(
+                map.range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+                    .take_while(move |(k, _)| (*k).borrow().starts_with(prefix)),
+            )),
+            KeyHint::Range(start, end) if is_empty_range(&(start, end)) => {
+                Box::new(std::iter::empty())
+            }
+            KeyHint::Range(start, end) => Box::new(rekey(map.range::<str, _>((start, end)))),
+        }
+    }
+    let any: &dyn Any = map;
+    if let Some(map) = any.downcast_ref::<BTreeMap<String, V>>() {
+        Some(get(map, hint))
+    } else {
+        any.downcast_ref::<BTreeMap<&'static str, V>>()
+            .map(|map| get(map, hint))
+    }
+}
+
+/// `S` has to be `'static` so maps with string keys can be looked up, see [`Data::query_links`]
+impl<K, V, S: BuildHasher + 'static> Data for HashMap<K, V, S>
 where
     K: Data + ToOwned + 'static,
     K::Owned: Data,
@@ -261,16 +475,85 @@ where
         links: &mut dyn Links,
         query: &crate::query::Query,
     ) -> Result<(), LinkError> {
-        use crate::query::Filter;
-        links.extend(self.iter().filter_map(|(k, v)| {
-            if query.matches_owned((k, v)) {
-                Some((k.to_owned(), v.to_owned()))
-            } else {
-                None
+        let matching = |(k, v): (&K, &V)| {
+            query
+                .matches_typed(&(k, v))
+                .then(|| (k.to_owned(), v.to_owned()))
+        };
+        if let Some(KeyHint::Exact(keys)) = query.filter().key_hint() {
+            if let Some(entries) = hash_lookup(self, keys) {
+                links.extend(entries.filter_map(matching))?;
+                return Ok(());
             }
-        }))?;
+        }
+        links.extend(self.iter().filter_map(matching))?;
+        Ok(())
+    }
+
+    #[inline]
+    fn supports_filter(&self, filter: &LinkFilter) -> bool {
+        match filter {
+            LinkFilter::Any | LinkFilter::None => true,
+            filter => str_keys::<K>() && filter.key_hint().is_some_and(|h| h.is_exact()),
+        }
+    }
+
+    #[inline]
+    fn iter_links(&self) -> Option<LinkIter<'_>> {
+        Some(LinkIter::new(
+            self.iter().map(|(k, t)| (k.to_owned(), t.to_owned())),
+        ))
+    }
+}
+
+impl<K, V> Data for BTreeMap<K, V>
+where
+    K: Data + ToOwned + 'static,
+    K::Owned: Data,
+    V: Data + ToOwned + 'static,
+    V::Owned: Data,
+{
+    #[inline]
+    fn provide_links(&self, links: &mut dyn Links) -> Result<(), LinkError> {
+        links.extend(self.iter().map(|(k, t)| (k.to_owned(), t.to_owned())))?;
+        Ok(())
+    }
+
+    #[inline]
+    fn query_links(
+        &self,
+        links: &mut dyn Links,
+        query: &crate::query::Query,
+    ) -> Result<(), LinkError> {
+        let matching = |(k, v): (&K, &V)| {
+            query
+                .matches_typed(&(k, v))
+                .then(|| (k.to_owned(), v.to_owned()))
+        };
+        if let Some(hint) = query.filter().key_hint() {
+            if let Some(entries) = btree_lookup(self, hint) {
+                links.extend(entries.filter_map(matching))?;
+                return Ok(());
+            }
+        }
+        links.extend(self.iter().filter_map(matching))?;
         Ok(())
     }
+
+    #[inline]
+    fn supports_filter(&self, filter: &LinkFilter) -> bool {
+        match filter {
+            LinkFilter::Any | LinkFilter::None => true,
+            filter => str_keys::<K>() && filter.key_hint().is_some(),
+        }
+    }
+
+    #[inline]
+    fn iter_links(&self) -> Option<LinkIter<'_>> {
+        Some(LinkIter::new(
+            self.iter().map(|(k, t)| (k.to_owned(), t.to_owned())),
+        ))
+    }
 }
 
 impl<T> Data for Vec<T>
@@ -284,15 +567,19 @@ where
         Ok(())
     }
 
+    #[inline]
+    fn iter_links(&self) -> Option<LinkIter<'_>> {
+        Some(LinkIter::new(self.iter().map(ToOwned::to_owned)))
+    }
+
     #[inline]
     fn query_links(
         &self,
         links: &mut dyn Links,
         query: &crate::query::Query,
     ) -> Result<(), LinkError> {
-        use crate::query::Filter;
         links.extend(self.iter().filter_map(|v| {
-            if Filter::<T>::matches(query, v) {
+            if query.matches_typed(v) {
                 Some(v.to_owned())
             } else {
                 None
@@ -304,7 +591,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::data::DataExt;
+    use crate::data::{BoxedData, Data, DataExt};
 
     #[test]
     fn string() {
@@ -326,4 +613,124 @@ mod tests {
 
         assert_eq!(DataExt::as_bool(&b), Some(true));
     }
+
+    #[test]
+    #[cfg(feature = "well_known")]
+    fn path_links() {
+        use crate::well_known::{NameType, ParentType, WellKnown};
+        use std::path::PathBuf;
+
+        let path = PathBuf::from("foo/bar.txt");
+        let links: Vec<(BoxedData, BoxedData)> = path.collect_links().unwrap();
+
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].0.get_id(), Some(NameType::ID));
+        assert_eq!(links[0].1.as_str(), Some("bar.txt".into()));
+        assert_eq!(links[1].0.get_id(), Some(ParentType::ID));
+        assert_eq!(links[1].1.as_str(), Some("foo".into()));
+
+        // The parent doesn't link further up
+        let deep = PathBuf::from("a/b/c/d");
+        let links: Vec<(BoxedData, BoxedData)> = deep.collect_links().unwrap();
+        assert_eq!(links[1].1.as_str(), Some("a/b/c".into()));
+        assert!(!links[1].1.has_links().unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "well_known")]
+    fn metadata_links() {
+        use crate::well_known::{fs::SizeType, WellKnown};
+
+        let metadata = std::fs::metadata(file!()).unwrap();
+        let links: Vec<(BoxedData, BoxedData)> = metadata.collect_links().unwrap();
+
+        let size = links
+            .iter()
+            .find(|(key, _)| key.get_id() == Some(SizeType::ID))
+            .unwrap();
+        assert_eq!(size.1.as_u64(), Some(metadata.len()));
+    }
+
+    #[test]
+    #[cfg(feature = "well_known")]
+    fn socket_addr_keys() {
+        use crate::well_known::net::{IP, PORT};
+        use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+        let addr: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+
+        assert_eq!(addr.get_key(&PORT), Some(8080));
+        assert_eq!(addr.get_key(&IP), Some(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+    }
+
+    #[test]
+    fn duration() {
+        let duration = std::time::Duration::from_millis(1500);
+
+        assert_eq!(DataExt::as_f64(&duration), Some(1.5));
+    }
+
+    fn keys(links: &[(BoxedData, BoxedData)]) -> Vec<String> {
+        let mut keys: Vec<String> = links.iter().filter_map(|(k, _)| k.as_str()).collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn hashmap_lookup() {
+        use crate::query::{DataFilter, LinkFilter, Query};
+        use std::collections::HashMap;
+
+        let map: HashMap<&str, u8> = HashMap::from([("a", 1), ("b", 2), ("c", 3)]);
+        let query = Query::new(
+            LinkFilter::key(
+                DataFilter::exact("a") | DataFilter::exact("c") | DataFilter::exact("d"),
+            ) & LinkFilter::target(DataFilter::gt(1u8)),
+        );
+        assert!(map.supports_filter(query.filter()));
+        let links: Vec<(BoxedData, BoxedData)> = map.query(&query).unwrap();
+        assert_eq!(keys(&links), ["c"]);
+
+        let numbered: HashMap<u8, u8> = HashMap::from([(1, 1)]);
+        assert!(!numbered.supports_filter(query.filter()));
+    }
+
+    #[test]
+    fn btreemap_lookup() {
+        use crate::query::{DataFilter, LinkFilter, Query};
+        use std::collections::BTreeMap;
+
+        let map: BTreeMap<String, u8> = ["ab", "abc", "b", "ba", "c"]
+            .into_iter()
+            .map(|k| (k.to_owned(), 0))
+            .collect();
+
+        let prefix = Query::new(LinkFilter::key(DataFilter::prefix("ab")));
+        assert!(map.supports_filter(prefix.filter()));
+        let links: Vec<(BoxedData, BoxedData)> = map.query(&prefix).unwrap();
+        assert_eq!(keys(&links), ["ab", "abc"]);
+
+        let range = Query::new(LinkFilter::key(DataFilter::str_range("abc"..="ba")));
+        let links: Vec<(BoxedData, BoxedData)> = map.query(&range).unwrap();
+        assert_eq!(keys(&links), ["abc", "b", "ba"]);
+
+        let exact = Query::new(LinkFilter::key(DataFilter::exact("c")));
+        let links: Vec<(BoxedData, BoxedData)> = map.query(&exact).unwrap();
+        assert_eq!(keys(&links), ["c"]);
+
+        let text = Query::new(LinkFilter::key(DataFilter::text("c")));
+        assert!(map.supports_filter(text.filter()));
+        let links: Vec<(BoxedData, BoxedData)> = map.query(&text).unwrap();
+        assert_eq!(keys(&links), ["c"]);
+
+        for range in [
+            DataFilter::str_range("b".."a"),
+            DataFilter::str_range("b".."b"),
+            DataFilter::str_range("b"..="a"),
+        ] {
+            let inverted = Query::new(LinkFilter::key(range));
+            let links: Vec<(BoxedData, BoxedData)> = map.query(&inverted).unwrap();
+            assert!(links.is_empty());
+        }
+    }
 }
@@ -1,7 +1,8 @@
 use ::toml::value::{Date, Datetime, Offset, Table, Time, Value};
 
 use crate::data::Data;
-use crate::links::{LinkError, Links, LinksExt};
+use crate::links::{iter::LinkIter, LinkError, Links, LinksExt};
+use crate::query::{KeyHint, LinkFilter};
 use crate::rr::{provided::Provided, Query, Request};
 
 impl Data for Value {
@@ -47,6 +48,28 @@ impl Data for Value {
             _ => Ok(()),
         }
     }
+
+    #[inline]
+    fn supports_filter(&self, filter: &crate::query::LinkFilter) -> bool {
+        use Value as V;
+        match self {
+            V::Table(table) => table.supports_filter(filter),
+            V::Array(array) => array.supports_filter(filter),
+            V::Datetime(dt) => dt.supports_filter(filter),
+            _ => true,
+        }
+    }
+
+    #[inline]
+    fn iter_links(&self) -> Option<LinkIter<'_>> {
+        use Value as V;
+        match self {
+            V::Table(table) => table.iter_links(),
+            V::Array(array) => array.iter_links(),
+            V::Datetime(dt) => dt.iter_links(),
+            _ => Some(LinkIter::from_items(std::iter::empty())),
+        }
+    }
 }
 
 impl Data for Table {
@@ -62,16 +85,34 @@ impl Data for Table {
         links: &mut dyn Links,
         query: &crate::query::Query,
     ) -> Result<(), LinkError> {
-        use crate::query::Filter;
-        links.extend(self.iter().filter_map(|(k, v)| {
-            if query.matches_owned((k, v)) {
-                Some((k.to_owned(), v.to_owned()))
-            } else {
-                None
-            }
-        }))?;
+        let matching = |(k, v): (&String, &Value)| {
+            query
+                .matches_typed(&(k, v))
+                .then(|| (k.to_owned(), v.to_owned()))
+        };
+        if let Some(KeyHint::Exact(keys)) = query.filter().key_hint() {
+            let entries = keys.into_iter().filter_map(|k| self.get_key_value(k));
+            links.extend(entries.filter_map(matching))?;
+        } else {
+            links.extend(self.iter().filter_map(matching))?;
+        }
         Ok(())
     }
+
+    #[inline]
+    fn supports_filter(&self, filter: &LinkFilter) -> bool {
+        match filter {
+            LinkFilter::Any | LinkFilter::None => true,
+            filter => filter.key_hint().is_some_and(|h| h.is_exact()),
+        }
+    }
+
+    #[inline]
+    fn iter_links(&self) -> Option<LinkIter<'_>> {
+        Some(LinkIter::new(
+            self.iter().map(|(k, v)| (k.to_owned(), v.to_owned())),
+        ))
+    }
 }
 
 impl Data for Datetime {
@@ -184,4 +225,19 @@ mod tests {
 
         assert_eq!(items.len(), 3);
     }
+
+    #[test]
+    fn key_lookup() {
+        use crate::data::BoxedData;
+        use crate::query::{DataFilter, LinkFilter, Query};
+
+        let table = Table::from_str("a = 1\nb = 2\n").unwrap();
+        let query = Query::new(LinkFilter::key(
+            DataFilter::exact("a") | DataFilter::exact("x"),
+        ));
+        assert!(table.supports_filter(query.filter()));
+        let links: Vec<(BoxedData, BoxedData)> = table.query(&query).unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].1.as_i64(), Some(1));
+    }
 }
@@ -0,0 +1,39 @@
+use ::uuid::Uuid;
+
+use crate::data::{Data, Provided};
+use crate::rr::{Query, Request};
+
+impl Data for Uuid {
+    #[inline]
+    fn provide_value(&self, request: &mut Request) {
+        self.provide_requested(request).debug_assert_provided();
+    }
+
+    #[inline]
+    fn provide_requested<Q: Query>(&self, request: &mut Request<Q>) -> impl Provided {
+        request.provide_ref(self);
+        request.provide_u128(self.as_u128());
+        request.provide_bytes(self.as_bytes());
+        if request.requests::<String>() {
+            request.provide_str_owned(self.hyphenated().to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::DataExt;
+
+    #[test]
+    fn values() {
+        let uuid = Uuid::NAMESPACE_DNS;
+
+        assert_eq!(DataExt::as_u128(&uuid), Some(uuid.as_u128()));
+        assert_eq!(
+            DataExt::as_str(&uuid).unwrap(),
+            "6ba7b810-9dad-11d1-80b4-00c04fd430c8"
+        );
+        assert_eq!(DataExt::as_bytes(&uuid), Some(uuid.as_bytes().to_vec()));
+    }
+}
@@ -0,0 +1,830 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::ops::Bound;
+use std::rc::Rc;
+
+use crate::data::{BoxedData, Data};
+use crate::links::{iter::LinkIter, LinkError, Links, MaybeKeyed, CONTINUE};
+use crate::query::{is_empty_range, path, DataFilter, Filter, LinkFilter, Number, Query};
+use crate::rr::{typeset, Receiver, Request};
+use filters::TextFilter;
+
+/// A link kept by [`Indexed`], shared so it can be pushed any number of times
+type SharedLink = (Option<Rc<dyn Data>>, Rc<dyn Data>);
+
+/// How an index stores the records it points to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexKind {
+    /// Answers exact string and number filters
+    Hash,
+    /// Also answers numeric comparisons, prefixes and string ranges
+    Sorted,
+}
+
+/// Wrapper answering queries on nested fields of its links from secondary indexes
+///
+/// The links of the wrapped data are read once and kept, each index maps the values found
+/// at a key path below a link's target to that link. Queries of the shape
+/// `LinkFilter::target(DataFilter::linked(LinkFilter::key(DataFilter::exact("role")) & LinkFilter::target(value)))`
+/// are answered by an index on `["role"]` if it supports the `value` filter, everything else
+/// falls back to scanning the links.
+///
+/// The links are a snapshot. Lists of records can be changed with [`push`](Indexed::push),
+/// [`remove`](Indexed::remove) and [`replace`](Indexed::replace), which only re-index the
+/// affected record. Any other change, through [`update`](Indexed::update) or otherwise,
+/// needs a [`refresh`](Indexed::refresh), which reads all links again and rebuilds every index.
+///
+/// The snapshot is a shared copy of every link next to the wrapped data, so the records are
+/// held twice and `Indexed` is neither `Send` nor `Sync`. Wrap data which hands out cheap
+/// shared links, like `Vec<Rc<T>>`, to only pay for the pointers.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use datalink::data::{indexed::{IndexKind, Indexed}, BoxedData, DataExt};
+/// use datalink::query::{DataFilter, LinkFilter, Query};
+///
+/// let users = vec![
+///     HashMap::from([("name", "ada"), ("role", "admin")]),
+///     HashMap::from([("name", "bob"), ("role", "user")]),
+/// ];
+/// let users = Indexed::new(users).unwrap().with_index(&["role"], IndexKind::Hash).unwrap();
+///
+/// let admins = Query::new(LinkFilter::target(DataFilter::linked(
+///     LinkFilter::key(DataFilter::exact("role")) & LinkFilter::target(DataFilter::exact("admin")),
+/// )));
+/// let found: Vec<BoxedData> = users.query(&admins).unwrap();
+/// assert_eq!(found.len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct Indexed<D> {
+    data: D,
+    links: Vec<SharedLink>,
+    indexes: Vec<Index>,
+}
+
+impl<D: Data> Indexed<D> {
+    /// Wraps `data` without any indexes
+    ///
+    /// # Errors
+    /// Any error returned by [`Data::provide_links`].
+    #[inline]
+    pub fn new(data: D) -> Result<Self, LinkError> {
+        let mut indexed = Self {
+            data,
+            links: Vec::new(),
+            indexes: Vec::new(),
+        };
+        indexed.refresh()?;
+        Ok(indexed)
+    }
+
+    /// Adds an index on the values at `path` below each link
+    ///
+    /// # Errors
+    /// Any error returned while following `path`.
+    #[inline]
+    pub fn with_index(mut self, path: &[&str], kind: IndexKind) -> Result<Self, LinkError> {
+        self.add_index(path, kind)?;
+        Ok(self)
+    }
+
+    /// See [`with_index`](Indexed::with_index)
+    ///
+    /// # Errors
+    /// Any error returned while following `path`.
+    #[inline]
+    pub fn add_index(&mut self, path: &[&str], kind: IndexKind) -> Result<(), LinkError> {
+        let path = path.iter().map(|&k| k.to_owned()).collect();
+        let index = Index::new(path, kind).build(&self.links)?;
+        self.indexes.push(index);
+        Ok(())
+    }
+
+    /// Reads the links of the wrapped data again and rebuilds all indexes
+    ///
+    /// # Errors
+    /// Any error returned by [`Data::provide_links`] or while following index paths,
+    /// the previous links and indexes are kept then.
+    #[inline]
+    pub fn refresh(&mut self) -> Result<(), LinkError> {
+        let mut links = Snapshot(Vec::new());
+        self.data.provide_links(&mut links)?;
+        let indexes = self
+            .indexes
+            .iter()
+            .map(|index| Index::new(index.path.clone(), index.kind()).build(&links.0))
+            .collect::<Result<_, _>>()?;
+        self.links = links.0;
+        self.indexes = indexes;
+        Ok(())
+    }
+
+    /// Changes the wrapped data with `f` and [`refresh`](Indexed::refresh)es afterwards
+    ///
+    /// # Errors
+    /// Any error returned by [`refresh`](Indexed::refresh), queries keep answering from the
+    /// links read before `f` then.
+    #[inline]
+    pub fn update<R>(&mut self, f: impl FnOnce(&mut D) -> R) -> Result<R, LinkError> {
+        let result = f(&mut self.data);
+        self.refresh()?;
+        Ok(result)
+    }
+}
+
+impl<T: Data + Clone + 'static> Indexed<Vec<T>> {
+    /// Appends `record` and adds it to every index
+    ///
+    /// # Errors
+    /// Any error returned while following index paths, nothing is changed then.
+    #[inline]
+    pub fn push(&mut self, record: T) -> Result<(), LinkError> {
+        let keys = self.index_keys(&record)?;
+        let position = self.links.len();
+        for (index, keys) in self.indexes.iter_mut().zip(keys) {
+            index.insert(keys, position);
+        }
+        self.links.push((None, Rc::new(record.clone())));
+        self.data.push(record);
+        Ok(())
+    }
+
+    /// Removes the record at `position` and from every index
+    ///
+    /// # Errors
+    /// Any error returned while following index paths, nothing is changed then.
+    ///
+    /// # Panics
+    /// If `position` is out of bounds.
+    #[inline]
+    pub fn remove(&mut self, position: usize) -> Result<T, LinkError> {
+        let keys = self.index_keys(&*self.links[position].1)?;
+        for (index, keys) in self.indexes.iter_mut().zip(keys) {
+            index.remove(&keys, position);
+            index.shift_after(position);
+        }
+        self.links.remove(position);
+        Ok(self.data.remove(position))
+    }
+
+    /// Replaces the record at `position` and re-indexes it
+    ///
+    /// # Errors
+    /// Any error returned while following index paths, nothing is changed then.
+    ///
+    /// # Panics
+    /// If `position` is out of bounds.
+    #[inline]
+    pub fn replace(&mut self, position: usize, record: T) -> Result<T, LinkError> {
+        let old = self.index_keys(&*self.links[position].1)?;
+        let new = self.index_keys(&record)?;
+        for ((index, old), new) in self.indexes.iter_mut().zip(old).zip(new) {
+            index.remove(&old, position);
+            index.insert(new, position);
+        }
+        self.links[position] = (None, Rc::new(record.clone()));
+        Ok(std::mem::replace(&mut self.data[position], record))
+    }
+}
+
+impl<D> Indexed<D> {
+    #[inline]
+    pub const fn get_ref(&self) -> &D {
+        &self.data
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> D {
+        self.data
+    }
+
+    /// The values of `target` for each index
+    fn index_keys(&self, target: &dyn Data) -> Result<Vec<Vec<IndexKey>>, LinkError> {
+        self.indexes
+            .iter()
+            .map(|index| index.keys(target))
+            .collect()
+    }
+
+    /// Positions of the links that may match `filter`, if the indexes can tell
+    fn candidates(&self, filter: &LinkFilter) -> Option<Vec<usize>> {
+        match filter {
+            LinkFilter::Target(f) => self.indexes.iter().find_map(|index| {
+                let value = strip_path(f, &index.path)?;
+                index.lookup(value)
+            }),
+            LinkFilter::And(and) => and.iter().find_map(|f| self.candidates(f)),
+            LinkFilter::Or(or) => or.iter().try_fold(Vec::new(), |mut all, f| {
+                all.extend(self.candidates(f)?);
+                Some(all)
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl<D: Data> Data for Indexed<D> {
+    #[inline]
+    fn provide_value(&self, request: &mut Request) {
+        self.data.provide_value(request);
+    }
+
+    #[inline]
+    fn provide_links(&self, links: &mut dyn Links) -> Result<(), LinkError> {
+        for (key, target) in &self.links {
+            if push(links, key, target)?.is_break() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn query_links(&self, links: &mut dyn Links, query: &Query) -> Result<(), LinkError> {
+        let matching = |(key, target): &&SharedLink| {
+            query.matches(&MaybeKeyed::new(key.as_deref(), &**target))
+        };
+        let Some(mut positions) = self.candidates(query.filter()) else {
+            for link in self.links.iter().filter(matching) {
+                if push(links, &link.0, &link.1)?.is_break() {
+                    break;
+                }
+            }
+            return Ok(());
+        };
+        positions.sort_unstable();
+        positions.dedup();
+        for link in positions
+            .into_iter()
+            .map(|p| &self.links[p])
+            .filter(matching)
+        {
+            if push(links, &link.0, &link.1)?.is_break() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn supports_filter(&self, filter: &LinkFilter) -> bool {
+        matches!(filter, LinkFilter::Any | LinkFilter::None) || self.candidates(filter).is_some()
+    }
+
+    #[inline]
+    fn iter_links(&self) -> Option<LinkIter<'_>> {
+        let links = self.links.iter().map(|(key, target)| {
+            let key = key.clone().map(|key| Box::new(key) as BoxedData);
+            (key, Box::new(Rc::clone(target)) as BoxedData)
+        });
+        Some(LinkIter::from_items(links))
+    }
+
+    #[inline]
+    fn get_id(&self) -> Option<crate::id::ID> {
+        self.data.get_id()
+    }
+}
+
+fn push(
+    links: &mut dyn Links,
+    key: &Option<Rc<dyn Data>>,
+    target: &Rc<dyn Data>,
+) -> crate::links::Result {
+    let key = key.clone().map(|k| Box::new(k) as BoxedData);
+    links.push(Box::new(Rc::clone(target)), key)
+}
+
+/// Links collected as shared data, so they can be pushed again
+struct Snapshot(Vec<SharedLink>);
+
+impl Links for Snapshot {
+    #[inline]
+    fn push(&mut self, target: BoxedData, key: Option<BoxedData>) -> crate::links::Result {
+        self.0.push((key.map(Rc::from), Rc::from(target)));
+        CONTINUE
+    }
+}
+
+/// The filter on the values at `path`, if `filter` only follows `path`
+///
+/// Each step has to be `DataFilter::Linked` with a conjunction of `LinkFilter::Key` with
+/// the exact key and a `LinkFilter::Target`. Further parts of the conjunction only narrow
+/// the result down, so the index can still be used.
+fn strip_path<'f>(filter: &'f DataFilter, path: &[String]) -> Option<&'f DataFilter> {
+    let Some((step, rest)) = path.split_first() else {
+        return Some(filter);
+    };
+    let DataFilter::Linked(link) = filter else {
+        return None;
+    };
+    let LinkFilter::And(parts) = &**link else {
+        return None;
+    };
+    let keyed = parts
+        .iter()
+        .any(|p| matches!(p, LinkFilter::Key(DataFilter::Text(TextFilter::Exact(k))) if k == step));
+    if !keyed {
+        return None;
+    }
+    parts.iter().find_map(|p| match p {
+        LinkFilter::Target(f) => strip_path(f, rest),
+        _ => None,
+    })
+}
+
+#[derive(Debug)]
+struct Index {
+    path: Vec<String>,
+    map: Map,
+}
+
+#[derive(Debug)]
+enum Map {
+    Hash(HashMap<IndexKey, Vec<usize>>),
+    Sorted(BTreeMap<IndexKey, Vec<usize>>),
+}
+
+impl Index {
+    fn new(path: Vec<String>, kind: IndexKind) -> Self {
+        let map = match kind {
+            IndexKind::Hash => Map::Hash(HashMap::new()),
+            IndexKind::Sorted => Map::Sorted(BTreeMap::new()),
+        };
+        Self { path, map }
+    }
+
+    const fn kind(&self) -> IndexKind {
+        match self.map {
+            Map::Hash(_) => IndexKind::Hash,
+            Map::Sorted(_) => IndexKind::Sorted,
+        }
+    }
+
+    /// Adds the values below all `links` to this empty index
+    fn build(mut self, links: &[SharedLink]) -> Result<Self, LinkError> {
+        for (position, (_, target)) in links.iter().enumerate() {
+            let keys = self.keys(&**target)?;
+            self.insert(keys, position);
+        }
+        Ok(self)
+    }
+
+    /// The values at the path below `target`
+    fn keys(&self, target: &dyn Data) -> Result<Vec<IndexKey>, LinkError> {
+        let mut keys = Keys(Vec::new());
+        collect_keys(target, &self.path, &mut keys)?;
+        Ok(keys.0)
+    }
+
+    fn insert(&mut self, keys: Vec<IndexKey>, position: usize) {
+        for key in keys {
+            match &mut self.map {
+                Map::Hash(map) => map.entry(key).or_default().push(position),
+                Map::Sorted(map) => map.entry(key).or_default().push(position),
+            }
+        }
+    }
+
+    fn remove(&mut self, keys: &[IndexKey], position: usize) {
+        let without = |positions: &mut Vec<usize>| {
+            positions.retain(|&p| p != position);
+            positions.is_empty()
+        };
+        for key in keys {
+            match &mut self.map {
+                Map::Hash(map) => {
+                    if map.get_mut(key).is_some_and(without) {
+                        map.remove(key);
+                    }
+                }
+                Map::Sorted(map) => {
+                    if map.get_mut(key).is_some_and(without) {
+                        map.remove(key);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Moves the positions after a removed link down by one
+    fn shift_after(&mut self, removed: usize) {
+        let all: Box<dyn Iterator<Item = &mut Vec<usize>>> = match &mut self.map {
+            Map::Hash(map) => Box::new(map.values_mut()),
+            Map::Sorted(map) => Box::new(map.values_mut()),
+        };
+        for position in all.flatten().filter(|p| **p > removed) {
+            *position -= 1;
+        }
+    }
+
+    /// Positions of the links whose values may match `filter`, if the index can tell
+    fn lookup(&self, filter: &DataFilter) -> Option<Vec<usize>> {
+        use DataFilter as E;
+        let exact = match filter {
+            E::Text(TextFilter::Exact(s)) => Some(IndexKey::Str(s.clone())),
+            E::EqNum(n) => Some(IndexKey::Num(*n)),
+            _ => None,
+        };
+        if let Some(key) = exact {
+            let positions = match &self.map {
+                Map::Hash(map) => map.get(&key),
+                Map::Sorted(map) => map.get(&key),
+            };
+            return Some(positions.cloned().unwrap_or_default());
+        }
+        if let E::Or(or) = filter {
+            return or.iter().try_fold(Vec::new(), |mut all, f| {
+                all.extend(self.lookup(f)?);
+                Some(all)
+            });
+        }
+
+        let Map::Sorted(map) = &self.map else {
+            return None;
+        };
+        use Bound::{Excluded, Included, Unbounded};
+        // Bounds of the numbers and strings among the keys
+        let num_start = Excluded(IndexKey::Bool(true));
+        let num_end = Excluded(IndexKey::Str(String::new()));
+        let num = |n: &Number| IndexKey::Num(*n);
+        let str = |b: &Bound<String>| b.as_ref().map(|s| IndexKey::Str(s.clone()));
+        let range = match filter {
+            E::Lt(n) => (num_start, Excluded(num(n))),
+            E::Le(n) => (num_start, Included(num(n))),
+            E::Gt(n) => (Excluded(num(n)), num_end),
+            E::Ge(n) => (Included(num(n)), num_end),
+            E::Between(min, max) => (Included(num(min)), Included(num(max))),
+            E::StrRange(start, end) => (
+                match str(start) {
+                    // The empty string is the first string
+                    Unbounded => Included(IndexKey::Str(String::new())),
+                    bound => bound,
+                },
+                str(end),
+            ),
+            E::Prefix(p) => {
+                let positions = map
+                    .range((Included(IndexKey::Str(p.clone())), Unbounded))
+                    .take_while(|(k, _)| matches!(k, IndexKey::Str(s) if s.starts_with(p.as_str())))
+                    .flat_map(|(_, positions)| positions.iter().copied())
+                    .collect();
+                return Some(positions);
+            }
+            _ => return None,
+        };
+        if is_empty_range(&range) {
+            return Some(Vec::new());
+        }
+        Some(
+            map.range(range)
+                .flat_map(|(_, positions)| positions.iter().copied())
+                .collect(),
+        )
+    }
+}
+
+fn collect_keys(data: &dyn Data, path: &[String], keys: &mut Keys) -> Result<(), LinkError> {
+    let Some((step, rest)) = path.split_first() else {
+        data.provide_value(&mut Request::new_erased(keys));
+        return Ok(());
+    };
+    let mut targets: Vec<BoxedData> = Vec::new();
+    let query = path::step(step);
+    data.query_links(&mut targets, &query)?;
+    for target in targets {
+        collect_keys(&*target, rest, keys)?;
+    }
+    Ok(())
+}
+
+/// A value as stored in an index
+///
+/// Numbers are normalized so that equal numbers of different types are the same key,
+/// `NaN` sorts after all other numbers.
+#[derive(Debug, Clone)]
+enum IndexKey {
+    Bool(bool),
+    Num(Number),
+    Str(String),
+}
+
+impl IndexKey {
+    const fn rank(&self) -> u8 {
+        match self {
+            IndexKey::Bool(_) => 0,
+            IndexKey::Num(_) => 1,
+            IndexKey::Str(_) => 2,
+        }
+    }
+}
+
+impl Ord for IndexKey {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (IndexKey::Bool(a), IndexKey::Bool(b)) => a.cmp(b),
+            (IndexKey::Num(a), IndexKey::Num(b)) => a
+                .partial_cmp(b)
+                .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan())),
+            (IndexKey::Str(a), IndexKey::Str(b)) => a.cmp(b),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+impl PartialOrd for IndexKey {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for IndexKey {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for IndexKey {}
+
+impl Hash for IndexKey {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rank().hash(state);
+        match self {
+            IndexKey::Bool(b) => b.hash(state),
+            IndexKey::Str(s) => s.hash(state),
+            IndexKey::Num(n) => n.hash(state),
+        }
+    }
+}
+
+/// Receiver collecting all indexable values
+struct Keys(Vec<IndexKey>);
+
+impl Keys {
+    fn num(&mut self, n: impl Into<Number>) {
+        self.0.push(IndexKey::Num(n.into()));
+    }
+}
+
+impl Receiver for Keys {
+    #[inline]
+    fn bool(&mut self, value: bool) {
+        self.0.push(IndexKey::Bool(value));
+    }
+    #[inline]
+    fn i8(&mut self, value: i8) {
+        self.num(value);
+    }
+    #[inline]
+    fn u8(&mut self, value: u8) {
+        self.num(value);
+    }
+    #[inline]
+    fn i16(&mut self, value: i16) {
+        self.num(value);
+    }
+    #[inline]
+    fn u16(&mut self, value: u16) {
+        self.num(value);
+    }
+    #[inline]
+    fn i32(&mut self, value: i32) {
+        self.num(value);
+    }
+    #[inline]
+    fn u32(&mut self, value: u32) {
+        self.num(value);
+    }
+    #[inline]
+    fn i64(&mut self, value: i64) {
+        self.num(value);
+    }
+    #[inline]
+    fn u64(&mut self, value: u64) {
+        self.num(value);
+    }
+    #[inline]
+    fn i128(&mut self, value: i128) {
+        self.num(value);
+    }
+    #[inline]
+    fn u128(&mut self, value: u128) {
+        self.num(value);
+    }
+    #[inline]
+    fn f32(&mut self, value: f32) {
+        self.num(value);
+    }
+    #[inline]
+    fn f64(&mut self, value: f64) {
+        self.num(value);
+    }
+    #[inline]
+    fn str(&mut self, value: &str) {
+        self.0.push(IndexKey::Str(value.to_owned()));
+    }
+    #[inline]
+    fn str_owned(&mut self, value: String) {
+        self.0.push(IndexKey::Str(value));
+    }
+    #[inline]
+    fn accepting() -> impl typeset::TypeSet + 'static {
+        typeset::Or(
+            typeset::Or(typeset::STRING_LIKE, typeset::NUMERIC),
+            typeset::Only::<bool>::default(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::DataExt;
+
+    fn records() -> Vec<HashMap<&'static str, u32>> {
+        (0..10)
+            .map(|i| HashMap::from([("id", i), ("group", i % 3)]))
+            .collect()
+    }
+
+    fn field(name: &str, value: DataFilter) -> LinkFilter {
+        LinkFilter::target(path::linked_at(&[name], value))
+    }
+
+    fn ids(found: &[BoxedData]) -> Vec<u32> {
+        let mut ids: Vec<u32> = found
+            .iter()
+            .map(|r| {
+                let id: Vec<BoxedData> = r.query(&path::step("id")).unwrap();
+                id[0].as_u32().unwrap()
+            })
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn hash() {
+        let indexed = Indexed::new(records())
+            .unwrap()
+            .with_index(&["group"], IndexKind::Hash)
+            .unwrap();
+
+        let query = Query::new(field("group", DataFilter::eq_num(1.0)));
+        assert!(indexed.supports_filter(query.filter()));
+        let found: Vec<BoxedData> = indexed.query(&query).unwrap();
+        assert_eq!(ids(&found), [1, 4, 7]);
+
+        // Only sorted indexes answer ranges
+        let query = Query::new(field("group", DataFilter::lt(1u8)));
+        assert!(!indexed.supports_filter(query.filter()));
+        let found: Vec<BoxedData> = indexed.query(&query).unwrap();
+        assert_eq!(ids(&found), [0, 3, 6, 9]);
+    }
+
+    #[test]
+    fn sorted() {
+        let indexed = Indexed::new(records())
+            .unwrap()
+            .with_index(&["id"], IndexKind::Sorted)
+            .unwrap();
+
+        let query = Query::new(field("id", DataFilter::between(3u8, 5u8)));
+        assert!(indexed.supports_filter(query.filter()));
+        let found: Vec<BoxedData> = indexed.query(&query).unwrap();
+        assert_eq!(ids(&found), [3, 4, 5]);
+
+        let query =
+            Query::new(field("id", DataFilter::ge(7u8)) & field("group", DataFilter::eq_num(0u8)));
+        let found: Vec<BoxedData> = indexed.query(&query).unwrap();
+        assert_eq!(ids(&found), [9]);
+
+        let query = Query::new(field("id", DataFilter::between(5u8, 3u8)));
+        let found: Vec<BoxedData> = indexed.query(&query).unwrap();
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn update() {
+        let mut indexed = Indexed::new(records())
+            .unwrap()
+            .with_index(&["group"], IndexKind::Hash)
+            .unwrap();
+        indexed
+            .update(|records| records.retain(|r| r["id"] != 4))
+            .unwrap();
+
+        let query = Query::new(field("group", DataFilter::eq_num(1u8)));
+        let found: Vec<BoxedData> = indexed.query(&query).unwrap();
+        assert_eq!(ids(&found), [1, 7]);
+    }
+
+    #[test]
+    fn failed_refresh() {
+        #[derive(Debug, Clone)]
+        struct Record(u32, Rc<std::cell::Cell<bool>>);
+
+        impl Data for Record {
+            fn provide_links(&self, links: &mut dyn Links) -> Result<(), LinkError> {
+                if self.1.get() {
+                    return Err(LinkError::UnsupportedQuery);
+                }
+                links.push_keyed(Box::new(self.0), Box::new("id"))?;
+                Ok(())
+            }
+        }
+
+        let failing = Rc::new(std::cell::Cell::new(false));
+        let records = (0..10).map(|i| Record(i, Rc::clone(&failing))).collect();
+        let mut indexed = Indexed::new(records)
+            .unwrap()
+            .with_index(&["id"], IndexKind::Sorted)
+            .unwrap();
+
+        let result = indexed.update(|records: &mut Vec<Record>| {
+            records.truncate(2);
+            failing.set(true);
+        });
+        assert!(result.is_err());
+
+        // The old links and indexes are still consistent
+        failing.set(false);
+        let query = Query::new(field("id", DataFilter::ge(5u8)));
+        let found: Vec<BoxedData> = indexed.query(&query).unwrap();
+        assert_eq!(ids(&found), [5, 6, 7, 8, 9]);
+
+        indexed.refresh().unwrap();
+        let found: Vec<BoxedData> = indexed.query(&query).unwrap();
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn incremental() {
+        let mut indexed = Indexed::new(records())
+            .unwrap()
+            .with_index(&["group"], IndexKind::Hash)
+            .unwrap()
+            .with_index(&["id"], IndexKind::Sorted)
+            .unwrap();
+        let group = |g: u8| Query::new(field("group", DataFilter::eq_num(g)));
+
+        indexed
+            .push(HashMap::from([("id", 10), ("group", 1)]))
+            .unwrap();
+        let found: Vec<BoxedData> = indexed.query(&group(1)).unwrap();
+        assert_eq!(ids(&found), [1, 4, 7, 10]);
+
+        let removed = indexed.remove(4).unwrap();
+        assert_eq!(removed["id"], 4);
+        let found: Vec<BoxedData> = indexed.query(&group(1)).unwrap();
+        assert_eq!(ids(&found), [1, 7, 10]);
+
+        // Positions after the removed record moved down
+        let replaced = indexed
+            .replace(6, HashMap::from([("id", 70), ("group", 2)]))
+            .unwrap();
+        assert_eq!(replaced["id"], 7);
+        let found: Vec<BoxedData> = indexed.query(&group(1)).unwrap();
+        assert_eq!(ids(&found), [1, 10]);
+        let found: Vec<BoxedData> = indexed.query(&group(2)).unwrap();
+        assert_eq!(ids(&found), [2, 5, 8, 70]);
+
+        let query = Query::new(field("id", DataFilter::ge(8u8)));
+        let found: Vec<BoxedData> = indexed.query(&query).unwrap();
+        assert_eq!(ids(&found), [8, 9, 10, 70]);
+
+        // Same answers as indexing from scratch
+        let fresh = Indexed::new(indexed.get_ref().clone())
+            .unwrap()
+            .with_index(&["id"], IndexKind::Sorted)
+            .unwrap();
+        let expected: Vec<BoxedData> = fresh.query(&query).unwrap();
+        assert_eq!(ids(&found), ids(&expected));
+        assert_eq!(indexed.get_ref().len(), 10);
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn nested() {
+        let users = serde_json::json!([
+            {"name": "ada", "profile": {"role": "admin"}},
+            {"name": "bob", "profile": {"role": "user"}},
+            {"name": "eve", "profile": {"role": "administrator"}},
+        ]);
+        let indexed = Indexed::new(users)
+            .unwrap()
+            .with_index(&["profile", "role"], IndexKind::Sorted)
+            .unwrap();
+
+        let role = |f| LinkFilter::target(path::linked_at(&["profile", "role"], f));
+        let query = Query::new(role(DataFilter::prefix("admin")));
+        assert!(indexed.supports_filter(query.filter()));
+        let found: Vec<BoxedData> = indexed.query(&query).unwrap();
+        assert_eq!(found.len(), 2);
+    }
+}
@@ -0,0 +1,365 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::data::{BoxedData, Data};
+use crate::links::{LinkError, Links, Result, BREAK, CONTINUE};
+use crate::query::path::{steps, value_at};
+use crate::query::Query;
+use crate::value::Value;
+
+/// Which records of a [`Join`] are kept without a partner
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum JoinKind {
+    /// Only matching pairs
+    #[default]
+    Inner,
+    /// Also left records without a match
+    Left,
+    /// Also left and right records without a match
+    FullOuter,
+}
+
+/// Lazy hash join of the targets of two data on the values at a key path of each
+///
+/// Every time its links are requested, the right side is read into a hash table and the
+/// left side is streamed against it, so the join always reflects the current data.
+/// Each link target is a [`Joined`] pair, which links to its records with the keys
+/// `"left"` and `"right"`. Keys are compared with [`Value`] equality, except that numbers
+/// are equal across types, so a JSON `1` matches a `1u32`.
+/// Records without a value at the key path never match.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use datalink::data::{join::Join, BoxedData, DataExt};
+/// use datalink::query::{DataFilter, LinkFilter, Query};
+///
+/// let users = vec![
+///     HashMap::from([("id", 1u32), ("age", 36)]),
+///     HashMap::from([("id", 2u32), ("age", 17)]),
+/// ];
+/// let orders = vec![
+///     HashMap::from([("user", 1u32), ("total", 30)]),
+///     HashMap::from([("user", 1u32), ("total", 12)]),
+///     HashMap::from([("user", 2u32), ("total", 5)]),
+/// ];
+/// let joined = Join::new(users, &["id"], orders, &["user"]);
+///
+/// let adults = Query::new(LinkFilter::target(DataFilter::linked(
+///     LinkFilter::key(DataFilter::exact("left"))
+///         & LinkFilter::target(DataFilter::linked(
+///             LinkFilter::key(DataFilter::exact("age")) & LinkFilter::target(DataFilter::ge(18u8)),
+///         )),
+/// )));
+/// let found: Vec<BoxedData> = joined.query(&adults).unwrap();
+/// assert_eq!(found.len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct Join<L, R> {
+    left: L,
+    left_key: Vec<Query>,
+    right: R,
+    right_key: Vec<Query>,
+    kind: JoinKind,
+}
+
+impl<L: Data, R: Data> Join<L, R> {
+    /// Inner join of `left` and `right` on the values at `left_key` and `right_key`
+    #[inline]
+    #[must_use]
+    pub fn new(left: L, left_key: &[&str], right: R, right_key: &[&str]) -> Self {
+        Self {
+            left,
+            left_key: steps(left_key),
+            right,
+            right_key: steps(right_key),
+            kind: JoinKind::Inner,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn with_kind(mut self, kind: JoinKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> JoinKind {
+        self.kind
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> (L, R) {
+        (self.left, self.right)
+    }
+}
+
+impl<L: Data, R: Data> Data for Join<L, R> {
+    #[inline]
+    fn provide_links(&self, links: &mut dyn Links) -> Result<(), LinkError> {
+        let mut build = Build {
+            steps: &self.right_key,
+            rows: Vec::new(),
+            table: HashMap::new(),
+        };
+        self.right.provide_links(&mut build)?;
+
+        let mut probe = Probe {
+            steps: &self.left_key,
+            matched: vec![false; build.rows.len()],
+            build: &build,
+            kind: self.kind,
+            out: links,
+            done: false,
+        };
+        self.left.provide_links(&mut probe)?;
+        if probe.done || self.kind != JoinKind::FullOuter {
+            return Ok(());
+        }
+
+        let matched = std::mem::take(&mut probe.matched);
+        let unmatched = build.rows.iter().zip(matched);
+        for (row, _) in unmatched.filter(|(_, matched)| !matched) {
+            if probe.emit(None, Some(row))?.is_break() {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A pair of records produced by a [`Join`]
+#[derive(Debug, Clone)]
+pub struct Joined {
+    left: Option<Arc<dyn Data>>,
+    right: Option<Arc<dyn Data>>,
+}
+
+impl Joined {
+    #[inline]
+    #[must_use]
+    pub fn left(&self) -> Option<&dyn Data> {
+        self.left.as_deref()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn right(&self) -> Option<&dyn Data> {
+        self.right.as_deref()
+    }
+}
+
+impl Data for Joined {
+    #[inline]
+    fn provide_links(&self, links: &mut dyn Links) -> Result<(), LinkError> {
+        for (key, side) in [("left", &self.left), ("right", &self.right)] {
+            let Some(side) = side else {
+                continue;
+            };
+            if links
+                .push_keyed(Box::new(Arc::clone(side)), Box::new(key))?
+                .is_break()
+            {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads the right side into rows and a table from key to row indices
+struct Build<'k> {
+    steps: &'k [Query],
+    rows: Vec<Arc<dyn Data>>,
+    table: HashMap<Value, Vec<usize>>,
+}
+
+impl Links for Build<'_> {
+    #[inline]
+    fn push(&mut self, target: BoxedData, _key: Option<BoxedData>) -> Result {
+        if let Some(value) = value_at(target.as_ref(), self.steps)? {
+            let key = value.normalized_number();
+            self.table.entry(key).or_default().push(self.rows.len());
+        }
+        self.rows.push(target.into());
+        CONTINUE
+    }
+}
+
+/// Streams the left side against the table and pushes the pairs
+struct Probe<'k, 'b, 'l> {
+    steps: &'k [Query],
+    build: &'b Build<'k>,
+    matched: Vec<bool>,
+    kind: JoinKind,
+    out: &'l mut dyn Links,
+    done: bool,
+}
+
+impl Probe<'_, '_, '_> {
+    fn emit(&mut self, left: Option<&Arc<dyn Data>>, right: Option<&Arc<dyn Data>>) -> Result {
+        let pair = Joined {
+            left: left.cloned(),
+            right: right.cloned(),
+        };
+        let flow = self.out.push_unkeyed(Box::new(pair))?;
+        self.done = flow.is_break();
+        Ok(flow)
+    }
+}
+
+impl Links for Probe<'_, '_, '_> {
+    #[inline]
+    fn push(&mut self, target: BoxedData, _key: Option<BoxedData>) -> Result {
+        let build = self.build;
+        let rows = value_at(target.as_ref(), self.steps)?
+            .and_then(|value| build.table.get(&value.normalized_number()))
+            .map_or(&[][..], Vec::as_slice);
+        let left: Arc<dyn Data> = target.into();
+
+        if rows.is_empty() && self.kind != JoinKind::Inner {
+            return self.emit(Some(&left), None);
+        }
+        for &row in rows {
+            self.matched[row] = true;
+            if self.emit(Some(&left), Some(&build.rows[row]))?.is_break() {
+                return BREAK;
+            }
+        }
+        CONTINUE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::DataExt;
+    use crate::query::path::linked_at;
+    use crate::query::{DataFilter, LinkFilter};
+
+    fn users() -> Vec<HashMap<&'static str, u32>> {
+        vec![
+            HashMap::from([("id", 1), ("age", 36)]),
+            HashMap::from([("id", 2), ("age", 17)]),
+            HashMap::from([("id", 3), ("age", 52)]),
+        ]
+    }
+
+    fn orders() -> Vec<HashMap<&'static str, u32>> {
+        vec![
+            HashMap::from([("user", 1), ("total", 30)]),
+            HashMap::from([("user", 1), ("total", 12)]),
+            HashMap::from([("user", 2), ("total", 5)]),
+            HashMap::from([("user", 4), ("total", 8)]),
+        ]
+    }
+
+    fn field(data: &BoxedData, name: &str) -> Option<u32> {
+        let value = value_at(data.as_ref(), &steps(&[name])).unwrap()?;
+        value.as_number()?.try_into().ok()
+    }
+
+    /// The left `id` and right `user` of each pair, sorted
+    fn pairs(join: &Join<impl Data, impl Data>) -> Vec<(Option<u32>, Option<u32>)> {
+        let mut found: Vec<_> = join
+            .as_list()
+            .unwrap()
+            .iter()
+            .map(|pair| {
+                let sides = pair.as_items().unwrap();
+                let side = |name: &str, field_name: &str| {
+                    sides
+                        .iter()
+                        .find(|(key, _)| key.as_str().as_deref() == Some(name))
+                        .and_then(|(_, record)| field(record, field_name))
+                };
+                (side("left", "id"), side("right", "user"))
+            })
+            .collect();
+        found.sort_unstable();
+        found
+    }
+
+    #[test]
+    fn inner() {
+        let join = Join::new(users(), &["id"], orders(), &["user"]);
+        assert_eq!(
+            pairs(&join),
+            [(Some(1), Some(1)), (Some(1), Some(1)), (Some(2), Some(2))]
+        );
+    }
+
+    #[test]
+    fn left() {
+        let join = Join::new(users(), &["id"], orders(), &["user"]).with_kind(JoinKind::Left);
+        assert_eq!(
+            pairs(&join),
+            [
+                (Some(1), Some(1)),
+                (Some(1), Some(1)),
+                (Some(2), Some(2)),
+                (Some(3), None)
+            ]
+        );
+    }
+
+    #[test]
+    fn full_outer() {
+        let join = Join::new(users(), &["id"], orders(), &["user"]).with_kind(JoinKind::FullOuter);
+        assert_eq!(
+            pairs(&join),
+            [
+                (None, Some(4)),
+                (Some(1), Some(1)),
+                (Some(1), Some(1)),
+                (Some(2), Some(2)),
+                (Some(3), None)
+            ]
+        );
+    }
+
+    #[test]
+    fn across_number_types() {
+        let orders = vec![
+            HashMap::from([("user", 1u64)]),
+            HashMap::from([("user", 2u64)]),
+            HashMap::from([("user", 300u64)]),
+        ];
+        let join = Join::new(users(), &["id"], orders, &["user"]);
+        assert_eq!(pairs(&join), [(Some(1), Some(1)), (Some(2), Some(2))]);
+
+        let ids = vec![1.0f64, 2.5, -1.0];
+        let signed = vec![1i8, -1];
+        let join = Join::new(ids, &[], signed, &[]);
+        assert_eq!(join.as_list().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn query() {
+        let join = Join::new(users(), &["id"], orders(), &["user"]);
+        let large = Query::new(LinkFilter::target(linked_at(
+            &["right", "total"],
+            DataFilter::gt(10u8),
+        )));
+
+        let found: Vec<BoxedData> = join.query(&large).unwrap();
+        assert_eq!(found.len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn json() {
+        let users = serde_json::json!([{"id": 1, "name": "ada"}, {"id": 2, "name": "bob"}]);
+        let orders = serde_json::json!([{"user": 2, "item": "tea"}]);
+
+        let join = Join::new(users, &["id"], orders, &["user"]);
+        assert_eq!(join.as_list().unwrap().len(), 1);
+
+        // JSON numbers are u64, the Rust ids u32
+        let orders = serde_json::json!([{"user": 1, "item": "tea"}, {"user": 3.0, "item": "jam"}]);
+        let join = Join::new(self::users(), &["id"], orders, &["user"]);
+        assert_eq!(join.as_list().unwrap().len(), 2);
+    }
+}
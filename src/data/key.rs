@@ -0,0 +1,196 @@
+use std::marker::PhantomData;
+
+use crate::data::constant::Const;
+use crate::data::unique::Unique;
+use crate::data::{format, Data, DataExt, Provided};
+use crate::id::ID;
+use crate::links::{LinkError, Links};
+use crate::rr::Request;
+
+/// Key with a compile-time constant ID whose link targets are of type `T`
+///
+/// Use [`LinksExt::push_typed`](crate::links::LinksExt::push_typed) to link a target of the right type
+/// and [`DataExt::get_key`] to get it back without converting it by hand.
+///
+/// ```rust
+/// use datalink::prelude::*;
+/// use datalink::data::{key::Key, DataExt};
+/// use datalink::links::{LinkError, Links, LinksExt};
+///
+/// const WIDTH: Key<1234, u32> = Key::empty();
+///
+/// struct Image;
+///
+/// impl Data for Image {
+///     fn provide_links(&self, links: &mut dyn Links) -> Result<(), LinkError> {
+///         links.push_typed(&WIDTH, 640)?;
+///         Ok(())
+///     }
+/// }
+///
+/// assert_eq!(Image.get_key(&WIDTH), Some(640));
+/// ```
+#[repr(transparent)]
+pub struct Key<const I: u128, T, D: ?Sized = ()>
+where
+    for<'d> &'d D: Data,
+{
+    target: PhantomData<fn() -> T>,
+    inner: Const<I, D>,
+}
+
+impl<const I: u128, T, D: ?Sized> Key<I, T, D>
+where
+    for<'d> &'d D: Data,
+{
+    #[inline]
+    pub const fn new(data: D) -> Self
+    where
+        D: Sized,
+    {
+        Self {
+            target: PhantomData,
+            inner: Const::new(data),
+        }
+    }
+}
+
+impl<const I: u128, T> Key<I, T, ()> {
+    #[inline]
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self::new(())
+    }
+}
+
+impl<const I: u128, T, D: Clone> Clone for Key<I, T, D>
+where
+    for<'d> &'d D: Data,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            target: PhantomData,
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<const I: u128, T, D: Copy> Copy for Key<I, T, D> where for<'d> &'d D: Data {}
+
+impl<const I: u128, T, D: ?Sized> std::fmt::Debug for Key<I, T, D>
+where
+    for<'d> &'d D: Data,
+{
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.format::<format::DEBUG>().fmt(f)
+    }
+}
+
+impl<const I: u128, T, D: ?Sized, O: Data + ?Sized> PartialEq<O> for Key<I, T, D>
+where
+    for<'d> &'d D: Data,
+{
+    #[inline]
+    fn eq(&self, other: &O) -> bool {
+        other.get_id().is_some_and(|id| id == self.id())
+    }
+}
+impl<const I: u128, T, D: ?Sized> Eq for Key<I, T, D> where for<'d> &'d D: Data {}
+
+#[warn(clippy::missing_trait_methods)]
+impl<const I: u128, T, D: ?Sized> Data for Key<I, T, D>
+where
+    for<'d> &'d D: Data,
+{
+    #[inline]
+    fn provide_value(&self, request: &mut Request) {
+        self.inner.provide_value(request);
+    }
+
+    #[inline]
+    fn provide_requested<Q: crate::rr::Query>(&self, _request: &mut Request<Q>) -> impl Provided
+    where
+        Self: Sized,
+    {
+        crate::rr::provided::DefaultImpl
+    }
+
+    #[inline]
+    fn provide_links(&self, links: &mut dyn Links) -> Result<(), LinkError> {
+        self.inner.provide_links(links)
+    }
+
+    #[inline]
+    fn query_links(
+        &self,
+        links: &mut dyn Links,
+        query: &crate::query::Query,
+    ) -> Result<(), LinkError> {
+        self.inner.query_links(links, query)
+    }
+
+    #[inline]
+    fn supports_filter(&self, filter: &crate::query::LinkFilter) -> bool {
+        self.inner.supports_filter(filter)
+    }
+
+    #[inline]
+    fn iter_links(&self) -> Option<crate::links::iter::LinkIter<'_>> {
+        self.inner.iter_links()
+    }
+
+    #[inline(always)]
+    fn get_id(&self) -> Option<ID> {
+        self.inner.get_id()
+    }
+}
+
+impl<const I: u128, T, D: ?Sized> Unique for Key<I, T, D>
+where
+    for<'d> &'d D: Data,
+{
+    #[inline]
+    fn id(&self) -> ID {
+        self.inner.id()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::links::{LinksExt, Result};
+
+    const COUNT: Key<1234, u32> = Key::empty();
+    const LABEL: Key<1235, String, &str> = Key::new("label");
+
+    struct Counted;
+
+    impl Data for Counted {
+        fn provide_links(&self, links: &mut dyn Links) -> Result<()> {
+            links.push_typed(&LABEL, "counted".to_owned())?;
+            links.push_typed(&COUNT, 3)?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn get_key() {
+        assert_eq!(Counted.get_key(&COUNT), Some(3));
+        assert_eq!(Counted.get_key(&LABEL), Some("counted".to_owned()));
+    }
+
+    #[test]
+    fn missing_key() {
+        const OTHER: Key<1236, u32> = Key::empty();
+
+        assert_eq!(Counted.get_key(&OTHER), None);
+    }
+
+    #[test]
+    fn provides_inner() {
+        assert_eq!(LABEL.as_str(), Some("label".to_owned()));
+        assert_eq!(LABEL.id(), ID::try_new(1235).unwrap());
+    }
+}
@@ -3,7 +3,7 @@ use std::hash::Hash;
 use std::marker::PhantomData;
 
 use crate::data::{format, Data, DataExt};
-use crate::id::ID;
+use crate::id::{IdGenerator, ID};
 use crate::links::{LinkError, Links};
 use crate::rr::Request;
 
@@ -49,10 +49,16 @@ impl<D: Data + ?Sized, T: Borrow<D>> Fixed<D, T> {
         }
     }
 
+    /// Construct an `Fixed`, generating an ID if the data doesn't provide one
+    ///
+    /// Stateful generators can be borrowed through [`IdGenerator::as_fn`].
     #[inline]
     #[must_use]
-    pub fn new_with(data: T, f: impl FnOnce() -> ID) -> Self {
-        let id = data.borrow().get_id().unwrap_or_else(f);
+    pub fn new_with(data: T, mut generator: impl IdGenerator) -> Self {
+        let id = data
+            .borrow()
+            .get_id()
+            .unwrap_or_else(|| generator.generate());
         Self {
             data,
             id,
@@ -111,6 +117,14 @@ impl<D: Data + ?Sized, T: Borrow<D>> Data for Fixed<D, T> {
         self.as_ref().query_links(links, query)
     }
     #[inline]
+    fn supports_filter(&self, filter: &crate::query::LinkFilter) -> bool {
+        self.as_ref().supports_filter(filter)
+    }
+    #[inline]
+    fn iter_links(&self) -> Option<crate::links::iter::LinkIter<'_>> {
+        self.as_ref().iter_links()
+    }
+    #[inline]
     fn get_id(&self) -> Option<ID> {
         #[cfg(debug_assertions)]
         if let Some(id) = self.as_ref().get_id() {
@@ -162,8 +176,8 @@ pub trait MaybeUnique: Data + Sized {
 
     #[inline]
     #[must_use]
-    fn into_unique_with(self, f: impl FnOnce() -> ID) -> Fixed<Self, Self> {
-        Fixed::new_with(self, f)
+    fn into_unique_with(self, generator: impl IdGenerator) -> Fixed<Self, Self> {
+        Fixed::new_with(self, generator)
     }
 
     #[cfg(feature = "random")]
@@ -187,6 +201,21 @@ mod tests {
         let res = Fixed::<bool, _>::try_new(&data);
         assert!(res.is_err());
     }
+
+    #[test]
+    fn generators() {
+        use crate::id::generator::Seeded;
+
+        let a = "a".into_unique_with(Seeded::new(7));
+        let b = "b".into_unique_with(Seeded::new(7).as_fn());
+        assert_eq!(a.get_id(), b.get_id());
+
+        let mut ids = Seeded::new(7);
+        let c = "c".into_unique_with(ids.as_fn());
+        let d = "d".into_unique_with(ids.as_fn());
+        assert_eq!(a.get_id(), c.get_id());
+        assert_ne!(c.get_id(), d.get_id());
+    }
 }
 
 // #[derive(Debug)]
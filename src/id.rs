@@ -1,9 +1,12 @@
 use std::{
-    fmt::{Debug, Display},
+    fmt::{Debug, Display, LowerHex, UpperHex, Write},
     num::NonZeroU128,
     str::FromStr,
 };
 
+pub mod generator;
+pub use generator::IdGenerator;
+
 /// ID used for uniquely identifying `Data`
 ///
 /// By default uses `NonZeroU128` as the underlying type for the following reasons:
@@ -103,14 +106,361 @@ impl ID<NonZeroU128> {
     pub const unsafe fn new_unchecked(id: u128) -> Self {
         Self::from_raw(NonZeroU128::new_unchecked(id))
     }
+
+    /// Create a stable `ID` from a name, like `"my.namespace/thing"`
+    ///
+    /// The name is hashed with [`fnv1a_128`], so the same name always results in the same `ID`.
+    /// Use the [`id!`](crate::id!) macro to force evaluation at compile time.
+    ///
+    /// # Note
+    /// Can be used in const contexts
+    ///
+    /// ```rust
+    /// use datalink::id::ID;
+    ///
+    /// const THING: ID = ID::from_name("my.namespace/thing");
+    /// assert_eq!(THING, ID::from_name(&format!("my.namespace/{}", "thing")));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn from_name(name: &str) -> Self {
+        match NonZeroU128::new(fnv1a_128(name.as_bytes())) {
+            Some(id) => Self::from_raw(id),
+            // Practically unreachable, but has to map somewhere
+            None => Self::from_raw(NonZeroU128::MAX),
+        }
+    }
+
+    /// Get the raw value as `u128`
+    ///
+    /// # Note
+    /// Can be used in const contexts, for example to build a `Const`
+    ///
+    /// ```rust
+    /// use datalink::{data::constant::Const, id};
+    ///
+    /// const THING: Const<{ id!("my.namespace/thing").get() }> = Const::empty();
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn get(self) -> u128 {
+        self.0.get()
+    }
+}
+
+pub(crate) const FNV_OFFSET_BASIS: u128 = 0x6C62272E_07BB0142_62B82175_6295C58D;
+pub(crate) const FNV_PRIME: u128 = 0x00000000_01000000_00000000_0000013B;
+
+/// 128-bit FNV-1a hash of `bytes`
+///
+/// Can be used in const contexts.
+///
+/// ```rust
+/// use datalink::id::fnv1a_128;
+///
+/// const HASH: u128 = fnv1a_128(b"a");
+/// assert_eq!(HASH, 0xD228CB69_6F1A8CAF_78912B70_4E4A8964);
+/// ```
+#[inline]
+#[must_use]
+pub const fn fnv1a_128(bytes: &[u8]) -> u128 {
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u128;
+        hash = hash.wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+    hash
 }
 
-impl<T: FromStr> FromStr for ID<T> {
+/// Alphabet of Crockford's base32
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// String encodings of an `ID`
+///
+/// ```rust
+/// use datalink::id::{Encoding, ID};
+///
+/// let id = ID::parse("0x734BFA09_662B_477C_8B61_7E85B6C47645").unwrap();
+///
+/// assert_eq!(id.encode(Encoding::Decimal).to_string(), "153255713126729605399543247017657792069");
+/// assert_eq!(id.encode(Encoding::Hex).to_string(), "0x734bfa09662b477c8b617e85b6c47645");
+/// assert_eq!(id.encode(Encoding::Uuid).to_string(), "734bfa09-662b-477c-8b61-7e85b6c47645");
+/// assert_eq!(id.encode(Encoding::Base32).to_string(), "3K9FX0JSHB8XY8PRBYGPVC8XJ5");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(u8)]
+pub enum Encoding {
+    /// Decimal digits, as used by `Display`
+    #[default]
+    Decimal = 0,
+    /// 32 lowercase hex digits prefixed with `0x`
+    Hex = 1,
+    /// Hyphenated lowercase UUID form
+    Uuid = 2,
+    /// 26 digits of Crockford's base32
+    Base32 = 3,
+}
+
+impl Encoding {
+    /// Get the encoding from its `u8` representation, falling back to `Decimal`
+    ///
+    /// Useful for selecting an encoding through const generics.
+    #[inline]
+    #[must_use]
+    pub const fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Hex,
+            2 => Self::Uuid,
+            3 => Self::Base32,
+            _ => Self::Decimal,
+        }
+    }
+}
+
+/// An `ID` displayed in a specific `Encoding`
+#[derive(Debug, Clone, Copy)]
+pub struct Encoded {
+    id: ID,
+    encoding: Encoding,
+}
+
+impl Display for Encoded {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let id = self.id.0.get();
+        match self.encoding {
+            Encoding::Decimal => Display::fmt(&id, f),
+            Encoding::Hex => write!(f, "{id:#034x}"),
+            Encoding::Uuid => write!(
+                f,
+                "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+                id >> 96,
+                (id >> 80) & 0xFFFF,
+                (id >> 64) & 0xFFFF,
+                (id >> 48) & 0xFFFF,
+                id & 0xFFFF_FFFF_FFFF
+            ),
+            Encoding::Base32 => (0..26).rev().try_for_each(|i| {
+                let digit = (id >> (i * 5)) & 0x1F;
+                f.write_char(CROCKFORD[digit as usize].into())
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseIdError {
+    #[error("ID must not be zero")]
+    Zero,
+    #[error("ID is out of range")]
+    Overflow,
+    #[error("Invalid digit {0:?} in ID")]
+    InvalidDigit(char),
+    #[error("Unrecognized ID format")]
+    InvalidFormat,
+}
+
+impl ID<NonZeroU128> {
+    /// Display the `ID` in the given `Encoding`
+    #[inline]
+    #[must_use]
+    pub const fn encode(self, encoding: Encoding) -> Encoded {
+        Encoded { id: self, encoding }
+    }
+}
+
+/// Accumulate digits of `bits` bits each into a `u128`
+fn parse_digits(
+    mut digits: impl Iterator<Item = char>,
+    bits: u32,
+    digit: impl Fn(char) -> Option<u32>,
+) -> Result<u128, ParseIdError> {
+    digits.try_fold(0u128, |acc, c| {
+        let d = digit(c).ok_or(ParseIdError::InvalidDigit(c))?;
+        if acc.leading_zeros() < bits {
+            return Err(ParseIdError::Overflow);
+        }
+        Ok(acc << bits | u128::from(d))
+    })
+}
+
+fn crockford_digit(c: char) -> Option<u32> {
+    match c.to_ascii_uppercase() {
+        'O' => Some(0),
+        'I' | 'L' => Some(1),
+        c => CROCKFORD
+            .iter()
+            .position(|&d| char::from(d) == c)
+            .and_then(|d| d.try_into().ok()),
+    }
+}
+
+impl ID<NonZeroU128> {
+    /// Parse an `ID` rendered in any `Encoding`
+    ///
+    /// * `0x`-prefixed hex digits, optionally separated by `_`
+    /// * The hyphenated UUID form
+    /// * 26 digits of Crockford's base32, case-insensitive
+    /// * 32 hex digits
+    /// * Decimal digits
+    ///
+    /// Fixed-length forms are checked before decimal, so every non-decimal rendering
+    /// reads back as itself even when it only contains digits. Decimal strings of exactly
+    /// 26 or 32 digits are therefore ambiguous, parse those with [`ID::decode`].
+    ///
+    /// ```rust
+    /// use datalink::id::ID;
+    ///
+    /// let decimal = ID::parse("153255713126729605399543247017657792069").unwrap();
+    /// let hex = ID::parse("734bfa09662b477c8b617e85b6c47645").unwrap();
+    /// let uuid = ID::parse("734BFA09-662B-477C-8B61-7E85B6C47645").unwrap();
+    /// let base32 = ID::parse("3k9fx0jshb8xy8prbygpvc8xj5").unwrap();
+    ///
+    /// assert_eq!(decimal, hex);
+    /// assert_eq!(decimal, uuid);
+    /// assert_eq!(decimal, base32);
+    /// ```
+    ///
+    /// # Errors
+    /// If `s` is not an `ID` in any of the forms above.
+    #[inline]
+    pub fn parse(s: &str) -> Result<Self, ParseIdError> {
+        let encoding = if s.starts_with("0x") || s.starts_with("0X") {
+            Encoding::Hex
+        } else if s.len() == 36 && s.contains('-') {
+            Encoding::Uuid
+        } else if s.len() == 26 {
+            Encoding::Base32
+        } else if s.len() == 32 {
+            return from_u128(parse_digits(s.chars(), 4, hex_digit)?);
+        } else {
+            Encoding::Decimal
+        };
+        Self::decode(s, encoding)
+    }
+
+    /// Parse an `ID` rendered in exactly the given `Encoding`
+    ///
+    /// ```rust
+    /// use datalink::id::{Encoding, ID};
+    ///
+    /// let id = ID::try_new(32).unwrap();
+    /// let base32 = id.encode(Encoding::Base32).to_string();
+    ///
+    /// assert_eq!(base32, "00000000000000000000000010");
+    /// assert_eq!(ID::decode(&base32, Encoding::Base32), Ok(id));
+    /// assert_eq!(ID::decode(&base32, Encoding::Decimal).map(ID::get), Ok(10));
+    /// ```
+    ///
+    /// # Errors
+    /// If `s` is not an `ID` in `encoding`.
+    #[inline]
+    pub fn decode(s: &str, encoding: Encoding) -> Result<Self, ParseIdError> {
+        let raw = match encoding {
+            Encoding::Decimal if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) => {
+                s.parse().map_err(|_| ParseIdError::Overflow)?
+            }
+            Encoding::Hex => {
+                let digits = s
+                    .strip_prefix("0x")
+                    .or_else(|| s.strip_prefix("0X"))
+                    .filter(|digits| digits.chars().any(|c| c != '_'))
+                    .ok_or(ParseIdError::InvalidFormat)?;
+                parse_digits(digits.chars().filter(|&c| c != '_'), 4, hex_digit)?
+            }
+            Encoding::Uuid
+                if s.len() == 36
+                    && s.bytes()
+                        .enumerate()
+                        .all(|(i, b)| matches!(i, 8 | 13 | 18 | 23) == (b == b'-')) =>
+            {
+                parse_digits(s.chars().filter(|&c| c != '-'), 4, hex_digit)?
+            }
+            Encoding::Base32 if s.len() == 26 => parse_digits(s.chars(), 5, crockford_digit)?,
+            _ => return Err(ParseIdError::InvalidFormat),
+        };
+        from_u128(raw)
+    }
+}
+
+fn from_u128(raw: u128) -> Result<ID, ParseIdError> {
+    NonZeroU128::new(raw)
+        .map(ID::from_raw)
+        .ok_or(ParseIdError::Zero)
+}
+
+fn hex_digit(c: char) -> Option<u32> {
+    c.to_digit(16)
+}
+
+/// Raw `ID` values which can be parsed from a string, see [`FromStr`] for [`ID`]
+///
+/// The default `ID` reads every [`Encoding`] through [`ID::parse`], the integer types and
+/// `String` use their own `FromStr`.
+pub trait FromIdStr: Sized {
+    type Err;
+
+    /// Parse the raw value of an `ID`
+    ///
+    /// # Errors
+    /// If `s` is not a valid raw value.
+    fn from_id_str(s: &str) -> Result<Self, Self::Err>;
+}
+
+impl FromIdStr for NonZeroU128 {
+    type Err = ParseIdError;
+
+    #[inline]
+    fn from_id_str(s: &str) -> Result<Self, Self::Err> {
+        ID::parse(s).map(ID::into_raw)
+    }
+}
+
+macro_rules! from_id_str {
+    ($($type:ty),*) => {$(
+        impl FromIdStr for $type {
+            type Err = <$type as FromStr>::Err;
+
+            #[inline]
+            fn from_id_str(s: &str) -> Result<Self, Self::Err> {
+                s.parse()
+            }
+        }
+    )*};
+}
+
+from_id_str!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, String);
+from_id_str!(
+    std::num::NonZeroU8,
+    std::num::NonZeroU16,
+    std::num::NonZeroU32,
+    std::num::NonZeroU64,
+    std::num::NonZeroUsize,
+    std::num::NonZeroI8,
+    std::num::NonZeroI16,
+    std::num::NonZeroI32,
+    std::num::NonZeroI64,
+    std::num::NonZeroI128,
+    std::num::NonZeroIsize
+);
+
+/// Parses the raw `T`, for the default `ID` in any [`Encoding`]
+///
+/// ```rust
+/// use datalink::id::ID;
+///
+/// let uuid: ID = "734BFA09-662B-477C-8B61-7E85B6C47645".parse().unwrap();
+/// assert_eq!("0x734bfa09_662b477c_8b617e85_b6c47645".parse(), Ok(uuid));
+/// assert_eq!("42".parse::<ID<u64>>(), Ok(ID::new(42u64)));
+/// ```
+impl<T: FromIdStr> FromStr for ID<T> {
     type Err = T::Err;
 
     #[inline]
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        T::from_str(s).map(Self::from_raw)
+        T::from_id_str(s).map(Self::from_raw)
     }
 }
 
@@ -121,6 +471,26 @@ impl<T: Display> Display for ID<T> {
     }
 }
 
+impl LowerHex for ID<NonZeroU128> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        write!(f, "{:032x}", self.0)
+    }
+}
+
+impl UpperHex for ID<NonZeroU128> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+        write!(f, "{:032X}", self.0)
+    }
+}
+
 impl<T: Debug> Debug for ID<T> {
     #[inline]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -141,9 +511,229 @@ impl rand::distributions::Distribution<ID> for rand::distributions::Standard {
     }
 }
 
+#[cfg(feature = "uuid")]
+mod uuid {
+    use super::ID;
+    use ::uuid::Uuid;
+
+    impl ID {
+        /// Build an `ID` from a versioned UUID
+        #[inline]
+        fn from_versioned(uuid: Uuid) -> Self {
+            debug_assert_ne!(uuid.get_version_num(), 0);
+            // SAFETY: The version bits of a versioned UUID are non-zero
+            unsafe { Self::new_unchecked(uuid.as_u128()) }
+        }
+
+        /// Create a random (version 4) UUID based `ID`
+        ///
+        /// ```rust
+        /// use datalink::prelude::*;
+        ///
+        /// let unique = "Hello, world!".into_unique_with(ID::new_v4);
+        /// ```
+        #[inline]
+        #[must_use]
+        pub fn new_v4() -> Self {
+            Self::from_versioned(Uuid::new_v4())
+        }
+
+        /// Create a name based (version 5) UUID based `ID`
+        ///
+        /// The same namespace and name always result in the same `ID`.
+        ///
+        /// ```rust
+        /// use datalink::id::ID;
+        /// use uuid::Uuid;
+        ///
+        /// let a = ID::new_v5(Uuid::NAMESPACE_URL, b"https://example.com");
+        /// let b = ID::new_v5(Uuid::NAMESPACE_URL, b"https://example.com");
+        /// assert_eq!(a, b);
+        /// ```
+        #[inline]
+        #[must_use]
+        pub fn new_v5(namespace: impl Into<Uuid>, name: &[u8]) -> Self {
+            Self::from_versioned(Uuid::new_v5(&namespace.into(), name))
+        }
+
+        /// Create a time-ordered (version 7) UUID based `ID`
+        ///
+        /// ```rust
+        /// use datalink::prelude::*;
+        ///
+        /// let unique = "Hello, world!".into_unique_with(ID::new_v7);
+        /// ```
+        #[inline]
+        #[must_use]
+        pub fn new_v7() -> Self {
+            Self::from_versioned(Uuid::now_v7())
+        }
+    }
+
+    impl From<ID> for Uuid {
+        #[inline]
+        fn from(id: ID) -> Self {
+            Uuid::from_u128(id.into_raw().get())
+        }
+    }
+
+    /// Fails for the nil UUID
+    impl TryFrom<Uuid> for ID {
+        type Error = std::num::TryFromIntError;
+
+        #[inline]
+        fn try_from(uuid: Uuid) -> Result<Self, Self::Error> {
+            Self::try_new(uuid.as_u128())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn roundtrip() {
+            let uuid = Uuid::new_v4();
+            let id = ID::try_from(uuid).unwrap();
+
+            assert_eq!(Uuid::from(id), uuid);
+        }
+
+        #[test]
+        fn nil() {
+            let id: Result<ID, _> = Uuid::nil().try_into();
+            assert!(id.is_err());
+        }
+
+        #[test]
+        fn versions() {
+            assert_eq!(Uuid::from(ID::new_v4()).get_version_num(), 4);
+            assert_eq!(
+                Uuid::from(ID::new_v5(Uuid::NAMESPACE_OID, b"")).get_version_num(),
+                5
+            );
+            assert_eq!(Uuid::from(ID::new_v7()).get_version_num(), 7);
+        }
+
+        #[test]
+        fn v7_is_ordered() {
+            let a = ID::new_v7();
+            let b = ID::new_v7();
+            assert!(a < b);
+        }
+    }
+}
+
 impl<T> From<T> for ID<T> {
     #[inline]
     fn from(value: T) -> Self {
         Self::from_raw(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WELL_KNOWN: ID = unsafe { ID::new_unchecked(0x734BFA09_662B_477C_8B61_7E85B6C47645) };
+
+    #[test]
+    fn from_name() {
+        const TAG: ID = crate::id!("datalink/tag");
+
+        assert_eq!(TAG.get(), 0x3FF454D2_67713F1F_FD20812B_C95ED25A);
+        assert_eq!(TAG, ID::from_name("datalink/tag"));
+        assert_ne!(TAG, ID::from_name("datalink/type"));
+        assert_eq!(fnv1a_128(b""), FNV_OFFSET_BASIS);
+    }
+
+    const ENCODINGS: [Encoding; 4] = [
+        Encoding::Decimal,
+        Encoding::Hex,
+        Encoding::Uuid,
+        Encoding::Base32,
+    ];
+
+    #[test]
+    fn roundtrip() {
+        for encoding in ENCODINGS {
+            let encoded = WELL_KNOWN.encode(encoding).to_string();
+            assert_eq!(ID::parse(&encoded), Ok(WELL_KNOWN), "{encoding:?}");
+            assert_eq!(
+                ID::decode(&encoded, encoding),
+                Ok(WELL_KNOWN),
+                "{encoding:?}"
+            );
+        }
+        assert_eq!(WELL_KNOWN.to_string().parse::<ID>(), Ok(WELL_KNOWN));
+        for encoding in ENCODINGS {
+            let encoded = WELL_KNOWN.encode(encoding).to_string();
+            assert_eq!(encoded.parse::<ID>(), Ok(WELL_KNOWN), "{encoding:?}");
+        }
+        assert_eq!("42".parse::<ID<u64>>(), Ok(ID::new(42u64)));
+    }
+
+    #[test]
+    fn digit_only_roundtrip() {
+        for raw in [1, 10, 32, 0x1000, 10u128.pow(25)] {
+            let id = ID::try_new(raw).unwrap();
+            for encoding in ENCODINGS {
+                let encoded = id.encode(encoding).to_string();
+                assert_eq!(
+                    ID::decode(&encoded, encoding),
+                    Ok(id),
+                    "{encoding:?} {encoded}"
+                );
+                if encoding != Encoding::Decimal {
+                    assert_eq!(ID::parse(&encoded), Ok(id), "{encoding:?} {encoded}");
+                }
+            }
+        }
+        assert_eq!(ID::parse("00000000000000000000000010").map(ID::get), Ok(32));
+        assert_eq!(ID::parse("10").map(ID::get), Ok(10));
+    }
+
+    #[test]
+    fn small_values_are_padded() {
+        let id = unsafe { ID::new_unchecked(1) };
+
+        assert_eq!(
+            id.encode(Encoding::Uuid).to_string(),
+            "00000000-0000-0000-0000-000000000001"
+        );
+        assert_eq!(
+            id.encode(Encoding::Base32).to_string(),
+            "00000000000000000000000001"
+        );
+        assert_eq!(format!("{id:x}"), "00000000000000000000000000000001");
+    }
+
+    #[test]
+    fn crockford_aliases() {
+        let canonical = ID::parse("0000000000000000000000000A").unwrap();
+        let aliased = ID::parse("ooooooooooooooooooooooooOa").unwrap();
+        assert_eq!(canonical, aliased);
+        assert_eq!(
+            ID::parse("0000000000000000000000000I"),
+            ID::parse("0000000000000000000000000l")
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(ID::parse("0"), Err(ParseIdError::Zero));
+        assert_eq!(ID::parse(""), Err(ParseIdError::InvalidFormat));
+        assert_eq!(ID::parse("0x"), Err(ParseIdError::InvalidFormat));
+        assert_eq!(ID::parse("0x__"), Err(ParseIdError::InvalidFormat));
+        assert_eq!(ID::parse("0x0"), Err(ParseIdError::Zero));
+        assert_eq!(
+            ID::parse("0x1_0000_0000_0000_0000_0000_0000_0000_0000"),
+            Err(ParseIdError::Overflow)
+        );
+        assert_eq!(ID::parse("0xfoo"), Err(ParseIdError::InvalidDigit('o')));
+        assert_eq!(
+            ID::decode("734bfa09662b477c8b617e85b6c47645", Encoding::Hex),
+            Err(ParseIdError::InvalidFormat)
+        );
+    }
+}
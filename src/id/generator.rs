@@ -0,0 +1,209 @@
+use std::num::NonZeroU128;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::ID;
+
+/// Strategy for allocating new `ID`s
+///
+/// Every `FnMut() -> ID` is a generator, so functions like `rand::random` or `ID::new_v7` can be used directly.
+/// Generators are passed by value to `Fixed::new_with` or `MaybeUnique::into_unique_with`,
+/// or borrowed through [`IdGenerator::as_fn`] to keep their state.
+///
+/// ```rust
+/// use datalink::prelude::*;
+/// use datalink::id::generator::Seeded;
+/// use datalink::id::IdGenerator;
+///
+/// let mut ids = Seeded::new(42);
+///
+/// let a = "a".into_unique_with(ids.as_fn());
+/// let b = "b".into_unique_with(ids.as_fn());
+///
+/// assert_ne!(a.get_id(), b.get_id());
+/// ```
+pub trait IdGenerator {
+    fn generate(&mut self) -> ID;
+
+    /// Borrow the generator as a closure
+    #[inline]
+    fn as_fn(&mut self) -> impl FnMut() -> ID + '_
+    where
+        Self: Sized,
+    {
+        move || self.generate()
+    }
+}
+
+impl<F: FnMut() -> ID> IdGenerator for F {
+    #[inline]
+    fn generate(&mut self) -> ID {
+        self()
+    }
+}
+
+/// Random `ID`s from `rand::random`
+#[cfg(feature = "random")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Random;
+
+#[cfg(feature = "random")]
+impl IdGenerator for Random {
+    #[inline]
+    fn generate(&mut self) -> ID {
+        rand::random()
+    }
+}
+
+/// Deterministic pseudo-random `ID`s for reproducible tests
+///
+/// The same seed always yields the same sequence of `ID`s.
+/// Not suitable where `ID`s must be unpredictable.
+#[derive(Debug, Clone)]
+pub struct Seeded {
+    state: u64,
+}
+
+impl Seeded {
+    #[inline]
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// SplitMix64
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl IdGenerator for Seeded {
+    #[inline]
+    fn generate(&mut self) -> ID {
+        loop {
+            let high = u128::from(self.next_u64());
+            let low = u128::from(self.next_u64());
+            if let Some(id) = NonZeroU128::new(high << 64 | low) {
+                break ID::from_raw(id);
+            }
+        }
+    }
+}
+
+/// Increasing `ID`s from a process-wide counter, starting at `1`
+///
+/// `ID`s are unique within the process, but not across processes.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Counter;
+
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+impl IdGenerator for Counter {
+    #[inline]
+    fn generate(&mut self) -> ID {
+        let count = u128::from(COUNTER.fetch_add(1, Ordering::Relaxed)) + 1;
+        // SAFETY: Incremented from an u64, so it's never zero
+        unsafe { ID::new_unchecked(count) }
+    }
+}
+
+/// Time-ordered `ID`s in the layout of a ULID
+///
+/// The upper 48 bits hold the milliseconds since the unix epoch, the lower 80 bits are random.
+/// `ID`s generated by the same `Ulid` are strictly increasing, even within the same millisecond.
+#[cfg(feature = "random")]
+#[derive(Debug, Default, Clone)]
+pub struct Ulid {
+    last: u128,
+}
+
+#[cfg(feature = "random")]
+impl Ulid {
+    const RANDOM_BITS: u32 = 80;
+    const RANDOM_MASK: u128 = (1 << Self::RANDOM_BITS) - 1;
+
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { last: 0 }
+    }
+}
+
+#[cfg(feature = "random")]
+impl IdGenerator for Ulid {
+    #[inline]
+    fn generate(&mut self) -> ID {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis());
+        let timestamp = (millis & 0xFFFF_FFFF_FFFF) << Self::RANDOM_BITS;
+
+        let raw = if timestamp > self.last & !Self::RANDOM_MASK {
+            timestamp | (rand::random::<u128>() & Self::RANDOM_MASK)
+        } else {
+            // Same millisecond or clock went backwards, keep increasing
+            self.last.wrapping_add(1)
+        };
+        self.last = raw;
+
+        loop {
+            if let Some(id) = NonZeroU128::new(self.last) {
+                break ID::from_raw(id);
+            }
+            self.last = rand::random::<u128>() & Self::RANDOM_MASK;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_is_reproducible() {
+        let mut a = Seeded::new(1234);
+        let mut b = Seeded::new(1234);
+
+        for _ in 0..10 {
+            assert_eq!(a.generate(), b.generate());
+        }
+        assert_ne!(a.generate(), Seeded::new(4321).generate());
+    }
+
+    #[test]
+    fn counter_increases() {
+        let a = Counter.generate();
+        let b = Counter.generate();
+
+        assert!(a < b);
+    }
+
+    #[test]
+    fn closure() {
+        let id = ID::from_name("closure");
+        let mut generator = || id;
+
+        assert_eq!(generator.generate(), id);
+    }
+
+    #[test]
+    #[cfg(feature = "random")]
+    fn ulid_is_ordered() {
+        let mut ulid = Ulid::new();
+        let ids: Vec<ID> = (0..100).map(|_| ulid.generate()).collect();
+
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    #[cfg(feature = "random")]
+    fn random_differs() {
+        assert_ne!(Random.generate(), Random.generate());
+    }
+}
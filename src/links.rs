@@ -2,8 +2,11 @@ use std::ops::ControlFlow;
 
 use crate::data::{BoxedData, Data};
 
+pub mod aggregate;
 pub mod filtered;
+pub mod group;
 pub mod impls;
+pub mod iter;
 
 use filtered::Filtered;
 
@@ -120,6 +123,22 @@ pub trait LinksExt: Links {
         CONTINUE
     }
 
+    /// Push a link keyed with a typed [`Key`](crate::data::key::Key), checking the target type
+    #[cfg(feature = "unique")]
+    #[inline]
+    fn push_typed<const I: u128, T, K>(
+        &mut self,
+        key: &crate::data::key::Key<I, T, K>,
+        target: T,
+    ) -> Result
+    where
+        T: Data + 'static,
+        K: Clone + 'static,
+        for<'d> &'d K: Data,
+    {
+        self.push_keyed(Box::new(target), Box::new(key.clone()))
+    }
+
     #[inline]
     fn filter<'f, F: ?Sized>(&mut self, filter: &'f F) -> Filtered<'f, '_, Self, F> {
         Filtered {
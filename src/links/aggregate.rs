@@ -0,0 +1,345 @@
+//! Sinks which fold links into a single result instead of collecting them
+//!
+//! Numbers are read from each target through a value request, so targets
+//! which don't provide a number are skipped.
+//! Use [`At`] to aggregate a value behind a key path of each target.
+//!
+//! ```rust
+//! use datalink::data::DataExt;
+//! use datalink::links::aggregate::{At, Sum};
+//! use datalink::query::{LinkFilter, Query};
+//! use std::collections::HashMap;
+//!
+//! let items = vec![
+//!     HashMap::from([("price", 3u32)]),
+//!     HashMap::from([("price", 4u32)]),
+//! ];
+//!
+//! let total = items
+//!     .aggregate(&Query::new(LinkFilter::Any), At::new(&["price"], Sum::default()))
+//!     .unwrap();
+//! assert_eq!(total, 7u8.into());
+//! ```
+
+use std::collections::BTreeSet;
+
+use super::{Links, Result, CONTINUE};
+use crate::data::BoxedData;
+use crate::query::numeric::first_number;
+use crate::query::path::{first_value, follow, steps};
+use crate::query::{Number, Query};
+use crate::value::Value;
+
+/// A [`Links`] sink which produces a result once all links are pushed
+pub trait Aggregate: Links {
+    type Output;
+
+    #[must_use]
+    fn finish(self) -> Self::Output;
+}
+
+/// Number of links
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Count(usize);
+
+impl Links for Count {
+    #[inline]
+    fn push(&mut self, _target: BoxedData, _key: Option<BoxedData>) -> Result {
+        self.0 += 1;
+        CONTINUE
+    }
+}
+
+impl Aggregate for Count {
+    type Output = usize;
+
+    #[inline]
+    fn finish(self) -> usize {
+        self.0
+    }
+}
+
+/// Sum of all numeric targets
+///
+/// Integers are summed exactly, until a float is found or the sum overflows `i128`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Sum(Option<f64>, i128);
+
+impl Sum {
+    #[allow(clippy::cast_precision_loss)]
+    fn add(&mut self, number: Number) {
+        match (self.0.as_mut(), number.normalized()) {
+            (None, Number::Int(i)) => match self.1.checked_add(i) {
+                Some(sum) => self.1 = sum,
+                None => self.0 = Some(self.1 as f64 + i as f64),
+            },
+            (None, n) => self.0 = Some(self.1 as f64 + to_f64(n)),
+            (Some(sum), n) => *sum += to_f64(n),
+        }
+    }
+}
+
+impl Links for Sum {
+    #[inline]
+    fn push(&mut self, target: BoxedData, _key: Option<BoxedData>) -> Result {
+        if let Some(number) = first_number(target.as_ref()) {
+            self.add(number);
+        }
+        CONTINUE
+    }
+}
+
+impl Aggregate for Sum {
+    type Output = Number;
+
+    #[inline]
+    fn finish(self) -> Number {
+        match self.0 {
+            Some(sum) => Number::Float(sum),
+            None => Number::Int(self.1),
+        }
+    }
+}
+
+/// Smallest numeric target, ignoring `NaN`
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Min(Option<Number>);
+
+impl Links for Min {
+    #[inline]
+    fn push(&mut self, target: BoxedData, _key: Option<BoxedData>) -> Result {
+        if let Some(number) = first_number(target.as_ref()) {
+            if !number.is_nan() && self.0.is_none_or(|min| number < min) {
+                self.0 = Some(number);
+            }
+        }
+        CONTINUE
+    }
+}
+
+impl Aggregate for Min {
+    type Output = Option<Number>;
+
+    #[inline]
+    fn finish(self) -> Option<Number> {
+        self.0
+    }
+}
+
+/// Largest numeric target, ignoring `NaN`
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Max(Option<Number>);
+
+impl Links for Max {
+    #[inline]
+    fn push(&mut self, target: BoxedData, _key: Option<BoxedData>) -> Result {
+        if let Some(number) = first_number(target.as_ref()) {
+            if !number.is_nan() && self.0.is_none_or(|max| number > max) {
+                self.0 = Some(number);
+            }
+        }
+        CONTINUE
+    }
+}
+
+impl Aggregate for Max {
+    type Output = Option<Number>;
+
+    #[inline]
+    fn finish(self) -> Option<Number> {
+        self.0
+    }
+}
+
+/// Arithmetic mean of all numeric targets
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Mean(f64, usize);
+
+impl Links for Mean {
+    #[inline]
+    fn push(&mut self, target: BoxedData, _key: Option<BoxedData>) -> Result {
+        if let Some(number) = first_number(target.as_ref()) {
+            self.0 += to_f64(number);
+            self.1 += 1;
+        }
+        CONTINUE
+    }
+}
+
+impl Aggregate for Mean {
+    type Output = Option<f64>;
+
+    #[allow(clippy::cast_precision_loss)]
+    #[inline]
+    fn finish(self) -> Option<f64> {
+        (self.1 > 0).then(|| self.0 / self.1 as f64)
+    }
+}
+
+/// The first value of each target, without duplicates and in value order
+///
+/// Only the first value is taken, as numbers are also provided as narrower, possibly
+/// truncated copies.
+#[derive(Debug, Default)]
+pub struct Distinct(BTreeSet<Value>);
+
+impl Links for Distinct {
+    #[inline]
+    fn push(&mut self, target: BoxedData, _key: Option<BoxedData>) -> Result {
+        if let Some(value) = first_value(target.as_ref()) {
+            self.0.insert(value);
+        }
+        CONTINUE
+    }
+}
+
+impl Aggregate for Distinct {
+    type Output = BTreeSet<Value>;
+
+    #[inline]
+    fn finish(self) -> BTreeSet<Value> {
+        self.0
+    }
+}
+
+/// Aggregates the data at a key path of each target instead of the target itself
+///
+/// Each step follows all links whose key is exactly the step,
+/// so a path can fan out into more than one value per target.
+#[derive(Debug)]
+pub struct At<A> {
+    steps: Vec<Query>,
+    inner: A,
+}
+
+impl<A: Aggregate> At<A> {
+    #[inline]
+    #[must_use]
+    pub fn new(path: &[&str], inner: A) -> Self {
+        Self {
+            steps: steps(path),
+            inner,
+        }
+    }
+}
+
+impl<A: Aggregate> Links for At<A> {
+    #[inline]
+    fn push(&mut self, target: BoxedData, key: Option<BoxedData>) -> Result {
+        follow(&self.steps, &mut self.inner, target, key)
+    }
+}
+
+impl<A: Aggregate> Aggregate for At<A> {
+    type Output = A::Output;
+
+    #[inline]
+    fn finish(self) -> A::Output {
+        self.inner.finish()
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn to_f64(number: Number) -> f64 {
+    match number {
+        Number::Int(i) => i as f64,
+        Number::UInt(u) => u as f64,
+        Number::Float(f) => f,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::DataExt;
+    use crate::query::path::linked_at;
+    use crate::query::{DataFilter, LinkFilter};
+    use std::collections::HashMap;
+
+    const ALL: Query = Query::new(LinkFilter::Any);
+
+    #[test]
+    fn numbers() {
+        let v = vec![3u8, 1, 4, 1, 5];
+
+        assert_eq!(v.aggregate(&ALL, Count::default()).unwrap(), 5);
+        assert_eq!(v.aggregate(&ALL, Sum::default()).unwrap(), 14u8.into());
+        assert_eq!(v.aggregate(&ALL, Min::default()).unwrap(), Some(1u8.into()));
+        assert_eq!(v.aggregate(&ALL, Max::default()).unwrap(), Some(5u8.into()));
+        assert_eq!(v.aggregate(&ALL, Mean::default()).unwrap(), Some(2.8));
+
+        let distinct = v.aggregate(&ALL, Distinct::default()).unwrap();
+        assert_eq!(distinct.len(), 4);
+        assert_eq!(distinct.first(), Some(&Value::U8(1)));
+
+        // One value per target, not its truncated copies
+        let wide = vec![300usize, 300];
+        let distinct = wide.aggregate(&ALL, Distinct::default()).unwrap();
+        assert_eq!(distinct.len(), 1);
+        assert!(!distinct.contains(&Value::U8(44)));
+    }
+
+    #[test]
+    fn empty() {
+        let v: Vec<u8> = vec![];
+
+        assert_eq!(v.aggregate(&ALL, Sum::default()).unwrap(), 0u8.into());
+        assert_eq!(v.aggregate(&ALL, Min::default()).unwrap(), None);
+        assert_eq!(v.aggregate(&ALL, Mean::default()).unwrap(), None);
+    }
+
+    #[test]
+    fn sum_overflow() {
+        let v = vec![i128::MAX, 1];
+        let sum = v.aggregate(&ALL, Sum::default()).unwrap();
+        assert!(matches!(sum, Number::Float(_)));
+
+        let v = vec![1.5f64, 1.0];
+        assert_eq!(v.aggregate(&ALL, Sum::default()).unwrap(), 2.5.into());
+    }
+
+    #[test]
+    fn skips_non_numeric() {
+        let v = vec!["a", "1"];
+        assert_eq!(v.aggregate(&ALL, Count::default()).unwrap(), 2);
+        assert_eq!(v.aggregate(&ALL, Max::default()).unwrap(), None);
+    }
+
+    #[test]
+    fn at_path() {
+        let items = vec![
+            HashMap::from([("price", 3u32), ("stock", 1)]),
+            HashMap::from([("price", 10u32), ("stock", 0)]),
+            HashMap::from([("stock", 2u32)]),
+        ];
+        let in_stock = Query::new(LinkFilter::target(linked_at(
+            &["stock"],
+            DataFilter::gt(0u8),
+        )));
+
+        let sum = items
+            .aggregate(&in_stock, At::new(&["price"], Sum::default()))
+            .unwrap();
+        assert_eq!(sum, 3u8.into());
+
+        let count = items
+            .aggregate(&ALL, At::new(&["price"], Count::default()))
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn nested_path() {
+        let data = serde_json::json!([
+            {"item": {"price": 2.5}},
+            {"item": {"price": 1}},
+            {"item": {}},
+        ]);
+
+        let mean = data
+            .aggregate(&ALL, At::new(&["item", "price"], Mean::default()))
+            .unwrap();
+        assert_eq!(mean, Some(1.75));
+    }
+}
@@ -0,0 +1,272 @@
+//! Bucketing links by a value at a key path of each target
+//!
+//! ```rust
+//! use datalink::data::DataExt;
+//! use datalink::links::aggregate::Count;
+//! use datalink::links::group::GroupBy;
+//! use datalink::query::{LinkFilter, Query};
+//! use datalink::value::Value;
+//! use std::collections::HashMap;
+//!
+//! let logs = vec![
+//!     HashMap::from([("level", "info")]),
+//!     HashMap::from([("level", "warn")]),
+//!     HashMap::from([("level", "info")]),
+//! ];
+//!
+//! let counts = logs
+//!     .aggregate(&Query::new(LinkFilter::Any), GroupBy::with(&["level"], Count::default))
+//!     .unwrap();
+//! assert_eq!(counts.get(&Value::String("info".into())), Some(&2));
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use super::aggregate::Aggregate;
+use super::{Links, Result, CONTINUE};
+use crate::data::{BoxedData, Data};
+use crate::links::LinkError;
+use crate::query::path::{steps, value_at};
+use crate::query::{Number, Query};
+use crate::value::Value;
+
+/// Aggregate which keeps all targets as they are
+#[derive(Debug, Default)]
+pub struct Members(Vec<Arc<dyn Data>>);
+
+impl Links for Members {
+    #[inline]
+    fn push(&mut self, target: BoxedData, _key: Option<BoxedData>) -> Result {
+        self.0.push(target.into());
+        CONTINUE
+    }
+}
+
+impl Aggregate for Members {
+    type Output = Vec<Arc<dyn Data>>;
+
+    #[inline]
+    fn finish(self) -> Self::Output {
+        self.0
+    }
+}
+
+/// Groups targets by the first value at a key path and aggregates each group separately
+///
+/// Keys are compared with [`Value`] equality after [`Value::normalized_number`],
+/// so `1u32`, `1u64` and `1.0` share a group with the key `Value::I128(1)`.
+/// Targets without a value at the path are skipped.
+pub struct GroupBy<A, F = fn() -> A> {
+    steps: Vec<Query>,
+    new_group: F,
+    groups: BTreeMap<Value, A>,
+}
+
+impl GroupBy<Members> {
+    /// Groups targets into lists of members
+    #[inline]
+    #[must_use]
+    pub fn new(path: &[&str]) -> Self {
+        Self::with(path, Members::default)
+    }
+}
+
+impl<A: Aggregate, F: Fn() -> A> GroupBy<A, F> {
+    /// Groups targets into a new aggregate for each key
+    #[inline]
+    #[must_use]
+    pub fn with(path: &[&str], new_group: F) -> Self {
+        Self {
+            steps: steps(path),
+            new_group,
+            groups: BTreeMap::new(),
+        }
+    }
+}
+
+impl<A: std::fmt::Debug, F> std::fmt::Debug for GroupBy<A, F> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GroupBy")
+            .field("steps", &self.steps)
+            .field("groups", &self.groups)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Aggregate, F: Fn() -> A> Links for GroupBy<A, F> {
+    #[inline]
+    fn push(&mut self, target: BoxedData, key: Option<BoxedData>) -> Result {
+        let Some(value) = value_at(target.as_ref(), &self.steps)? else {
+            return CONTINUE;
+        };
+        let group = self
+            .groups
+            .entry(value.normalized_number())
+            .or_insert_with(|| (self.new_group)());
+        // A full group doesn't stop the others from growing
+        let _ = group.push(target, key)?;
+        CONTINUE
+    }
+}
+
+impl<A: Aggregate, F: Fn() -> A> Aggregate for GroupBy<A, F> {
+    type Output = Groups<A::Output>;
+
+    // `Value` is only `!Send` through `Value::Other`, keys share the `Arc` of the groups
+    #[allow(clippy::arc_with_non_send_sync)]
+    #[inline]
+    fn finish(self) -> Self::Output {
+        Groups(
+            self.groups
+                .into_iter()
+                .map(|(key, group)| (Arc::new(key), Arc::new(group.finish())))
+                .collect(),
+        )
+    }
+}
+
+/// Result of a [`GroupBy`], linking from each key to its group in key order
+#[derive(Debug)]
+pub struct Groups<T>(BTreeMap<Arc<Value>, Arc<T>>);
+
+impl<T> Groups<T> {
+    /// The group of a key, numbers of any type find the group of an equal number
+    #[inline]
+    #[must_use]
+    pub fn get(&self, key: &Value) -> Option<&T> {
+        match Number::try_from(key) {
+            Ok(number) => self.0.get(&Value::from(number.normalized())),
+            Err(()) => self.0.get(key),
+        }
+        .map(AsRef::as_ref)
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&Value, &T)> {
+        self.0
+            .iter()
+            .map(|(key, group)| (key.as_ref(), group.as_ref()))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<T: Data + 'static> Data for Groups<T> {
+    #[inline]
+    fn provide_links(&self, links: &mut dyn Links) -> Result<(), LinkError> {
+        for (key, group) in &self.0 {
+            let target = Box::new(Arc::clone(group));
+            if links
+                .push_keyed(target, Box::new(Arc::clone(key)))?
+                .is_break()
+            {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::DataExt;
+    use crate::links::aggregate::{At, Count, Sum};
+    use crate::query::LinkFilter;
+    use std::collections::HashMap;
+
+    const ALL: Query = Query::new(LinkFilter::Any);
+
+    fn logs() -> Vec<HashMap<&'static str, &'static str>> {
+        vec![
+            HashMap::from([("level", "info"), ("size", "3")]),
+            HashMap::from([("level", "warn"), ("size", "5")]),
+            HashMap::from([("level", "info"), ("size", "7")]),
+            HashMap::from([("size", "1")]),
+        ]
+    }
+
+    fn string(s: &str) -> Value {
+        Value::String(s.into())
+    }
+
+    #[test]
+    fn members() {
+        let groups = logs().group_by(&["level"]).unwrap();
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups.get(&string("info")).map(Vec::len), Some(2));
+        assert_eq!(groups.get(&string("warn")).map(Vec::len), Some(1));
+        assert!(groups.get(&string("debug")).is_none());
+    }
+
+    #[test]
+    fn as_data() {
+        let groups = logs().group_by(&["level"]).unwrap();
+
+        let items = groups.as_items().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].0.as_str().as_deref(), Some("info"));
+        assert_eq!(items[0].1.as_list().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn nested() {
+        let counts = logs()
+            .aggregate(&ALL, GroupBy::with(&["level"], Count::default))
+            .unwrap();
+        assert_eq!(counts.get(&string("info")), Some(&2));
+
+        let sizes = vec![
+            HashMap::from([("level", 1u8), ("size", 3)]),
+            HashMap::from([("level", 2u8), ("size", 5)]),
+            HashMap::from([("level", 1u8), ("size", 7)]),
+        ];
+        let sums = sizes
+            .aggregate(
+                &ALL,
+                GroupBy::with(&["level"], || At::new(&["size"], Sum::default())),
+            )
+            .unwrap();
+        assert_eq!(sums.get(&Value::U8(1)), Some(&10u8.into()));
+        assert_eq!(sums.get(&Value::U8(2)), Some(&5u8.into()));
+    }
+
+    #[test]
+    fn by_value() {
+        let groups = vec![1u8, 2, 1, 1].group_by(&[]).unwrap();
+        assert_eq!(groups.get(&Value::U8(1)).map(Vec::len), Some(3));
+    }
+
+    #[test]
+    fn across_number_types() {
+        let sizes = vec![Number::UInt(1), Number::Int(1), Number::Float(2.0)];
+        let groups = sizes.group_by(&[]).unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups.get(&Value::U32(1)).map(Vec::len), Some(2));
+        assert_eq!(groups.iter().next().unwrap().0, &Value::I128(1));
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn json_numbers() {
+        let rows: serde_json::Value =
+            serde_json::from_str(r#"[{"n": 1}, {"n": 1.0}, {"n": 1.5}]"#).unwrap();
+        let groups = rows.group_by(&["n"]).unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups.get(&Value::U64(1)).map(Vec::len), Some(2));
+        assert_eq!(groups.get(&Value::F64(1.5)).map(Vec::len), Some(1));
+    }
+}
@@ -0,0 +1,277 @@
+use super::{Link, Links, Result, BREAK, CONTINUE};
+use crate::data::{BoxedData, Data};
+use crate::links::LinkError;
+use crate::query::{Filter, LinkFilter, Query};
+
+/// A link as returned by [`LinkIter`], the key if there is one and the target
+pub type LinkItem = (Option<BoxedData>, BoxedData);
+
+/// Iterator over the links of a datum
+///
+/// Data which can hand out its links one at a time returns a lazy iterator from
+/// [`Data::iter_links`], for all other data the links are collected first.
+///
+/// ```rust
+/// use datalink::data::DataExt;
+///
+/// let v = vec![1u8, 2, 3, 4];
+/// let small = v.links().unwrap().take_while(|(_, t)| t.as_u8() < Some(3)).count();
+/// assert_eq!(small, 2);
+/// ```
+pub struct LinkIter<'a>(Inner<'a>);
+
+enum Inner<'a> {
+    Lazy(Box<dyn Iterator<Item = LinkItem> + 'a>),
+    Buffered(std::vec::IntoIter<LinkItem>),
+}
+
+impl<'a> LinkIter<'a> {
+    /// Lazily boxes the links of `links` as they're iterated
+    ///
+    /// Links whose [`Link::build_into`] fails are skipped.
+    #[inline]
+    pub fn new<L>(links: impl Iterator<Item = L> + 'a) -> Self
+    where
+        L: Link + 'a,
+        L::Key: 'static + Sized,
+        L::Target: 'static + Sized,
+    {
+        Self::from_items(links.filter_map(into_item))
+    }
+
+    #[inline]
+    pub fn from_items(items: impl Iterator<Item = LinkItem> + 'a) -> Self {
+        Self(Inner::Lazy(Box::new(items)))
+    }
+
+    /// Collects all links of `data` to iterate them
+    ///
+    /// # Errors
+    /// Any error returned by [`Data::provide_links`].
+    #[inline]
+    pub fn buffered(data: &(impl Data + ?Sized)) -> Result<Self, LinkError> {
+        let mut buffer = Buffer::default();
+        data.provide_links(&mut buffer)?;
+        Ok(buffer.into())
+    }
+
+    /// Collects the links of `data` matching `query` to iterate them
+    ///
+    /// # Errors
+    /// Any error returned by [`Data::query_links`].
+    #[inline]
+    pub fn buffered_query(data: &(impl Data + ?Sized), query: &Query) -> Result<Self, LinkError> {
+        let mut buffer = Buffer::default();
+        query.run(data, &mut buffer)?;
+        Ok(buffer.into())
+    }
+
+    /// Only the links matching `query`, up to its limit and projected to its selection
+    #[inline]
+    #[must_use]
+    pub fn matching(self, query: &'a Query) -> Self {
+        let filter = query.filter();
+        let matching = self
+            .filter(move |(key, target)| matches(filter, key.as_deref(), target.as_ref()))
+            .take(query.limit())
+            .map(|(key, target)| (key, query.project(target)));
+        Self::from_items(matching)
+    }
+
+    /// Whether the links were collected up front
+    #[inline]
+    #[must_use]
+    pub const fn is_buffered(&self) -> bool {
+        matches!(self.0, Inner::Buffered(_))
+    }
+}
+
+impl Iterator for LinkIter<'_> {
+    type Item = LinkItem;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            Inner::Lazy(iter) => iter.next(),
+            Inner::Buffered(iter) => iter.next(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.0 {
+            Inner::Lazy(iter) => iter.size_hint(),
+            Inner::Buffered(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl std::fmt::Debug for LinkIter<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LinkIter")
+            .field("buffered", &self.is_buffered())
+            .finish_non_exhaustive()
+    }
+}
+
+fn matches(filter: &LinkFilter, key: Option<&dyn Data>, target: &dyn Data) -> bool {
+    match key {
+        Some(key) => filter.matches_owned((key, target)),
+        None => <LinkFilter as Filter<dyn Data>>::matches(filter, target),
+    }
+}
+
+/// Boxes the key and target of `link`
+///
+/// [`Slot`] accepts any link, so only a [`Link::build_into`] that fails or pushes nothing on its
+/// own yields `None`, and that link is skipped.
+fn into_item<L>(link: L) -> Option<LinkItem>
+where
+    L: Link,
+    L::Key: 'static + Sized,
+    L::Target: 'static + Sized,
+{
+    let mut slot = Slot(None);
+    match link.build_into(&mut slot) {
+        Ok(_) => slot.0,
+        Err(_) => None,
+    }
+}
+
+/// Keeps the first link pushed
+struct Slot(Option<LinkItem>);
+
+impl Links for Slot {
+    #[inline]
+    fn push(&mut self, target: BoxedData, key: Option<BoxedData>) -> Result {
+        self.0.get_or_insert((key, target));
+        BREAK
+    }
+}
+
+#[derive(Default)]
+struct Buffer(Vec<LinkItem>);
+
+impl Links for Buffer {
+    #[inline]
+    fn push(&mut self, target: BoxedData, key: Option<BoxedData>) -> Result {
+        self.0.push((key, target));
+        CONTINUE
+    }
+}
+
+impl From<Buffer> for LinkIter<'_> {
+    #[inline]
+    fn from(buffer: Buffer) -> Self {
+        Self(Inner::Buffered(buffer.0.into_iter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::DataExt;
+    use crate::query::{DataFilter, Selection};
+    use std::collections::{BTreeMap, HashMap};
+
+    #[test]
+    fn lazy() {
+        let v = vec![1u8, 2, 3];
+        let links = v.links().unwrap();
+        assert!(!links.is_buffered());
+        let targets: Vec<_> = links.map(|(key, t)| (key.is_none(), t.as_u8())).collect();
+        assert_eq!(targets, [(true, Some(1)), (true, Some(2)), (true, Some(3))]);
+
+        let map = BTreeMap::from([("a", 1u8), ("b", 2)]);
+        let mut links = map.links().unwrap().peekable();
+        let (key, _) = links.peek().unwrap();
+        assert_eq!(key.as_ref().and_then(|k| k.as_str()).as_deref(), Some("a"));
+        assert_eq!(links.count(), 2);
+
+        let pairs = v.links().unwrap().zip(map.links().unwrap()).count();
+        assert_eq!(pairs, 2);
+    }
+
+    #[test]
+    fn new() {
+        let links = LinkIter::new(vec![("a", 1u8), ("b", 2)].into_iter());
+        let items: Vec<_> = links
+            .map(|(key, t)| (key.and_then(|k| k.as_str()).unwrap_or_default(), t.as_u8()))
+            .collect();
+        assert_eq!(items, [("a".into(), Some(1)), ("b".into(), Some(2))]);
+    }
+
+    #[test]
+    fn buffered() {
+        #[derive(Debug)]
+        struct Pushed;
+
+        impl Data for Pushed {
+            fn provide_links(&self, links: &mut dyn Links) -> Result<(), LinkError> {
+                links.push_unkeyed(Box::new(1u8))?;
+                links.push_keyed(Box::new(2u8), Box::new("two"))?;
+                Ok(())
+            }
+        }
+
+        let links = Pushed.links().unwrap();
+        assert!(links.is_buffered());
+        assert_eq!(links.filter(|(key, _)| key.is_some()).count(), 1);
+
+        let query = Query::new(LinkFilter::target(DataFilter::gt(1u8)));
+        let links = Pushed.query_iter(&query).unwrap();
+        assert_eq!(links.count(), 1);
+    }
+
+    #[test]
+    fn query() {
+        let map = HashMap::from([("a", 1u8), ("b", 2), ("c", 3)]);
+
+        let query = Query::new(LinkFilter::target(DataFilter::ge(2u8)));
+        let links = map.query_iter(&query).unwrap();
+        assert!(!links.is_buffered());
+        assert_eq!(links.count(), 2);
+
+        let query = Query::new(LinkFilter::Any).with_limit(2);
+        assert_eq!(map.query_iter(&query).unwrap().count(), 2);
+
+        // Key lookups are pushed down instead of scanning
+        let query = Query::new(LinkFilter::key(DataFilter::exact("b")));
+        let mut links = map.query_iter(&query).unwrap();
+        assert!(links.is_buffered());
+        assert_eq!(links.next().and_then(|(_, t)| t.as_u8()), Some(2));
+        assert!(links.next().is_none());
+    }
+
+    #[test]
+    fn selection() {
+        let users = vec![HashMap::from([("name", "ada"), ("password", "hunter2")])];
+        let query = Query::new(LinkFilter::Any).with_selection(Selection::new().field("name"));
+
+        let (_, user) = users.query_iter(&query).unwrap().next().unwrap();
+        assert_eq!(user.as_items().unwrap().len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn json() {
+        let value = serde_json::json!({"a": [1, 2], "b": null});
+        let links = value.links().unwrap();
+        assert!(!links.is_buffered());
+        assert_eq!(links.count(), 2);
+
+        let array = serde_json::json!([1, 2, 3]);
+        let sum: u64 = array.links().unwrap().filter_map(|(_, t)| t.as_u64()).sum();
+        assert_eq!(sum, 6);
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn toml() {
+        let value: ::toml::Value = ::toml::from_str("a = 1\nb = [2, 3]").unwrap();
+        let links = value.links().unwrap();
+        assert!(!links.is_buffered());
+        assert_eq!(links.count(), 2);
+    }
+}
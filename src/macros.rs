@@ -17,6 +17,144 @@ macro_rules! impl_unique {
     ($name:ident, $id:expr) => {};
 }
 
+/// Create a non-zero `ID` from a namespaced name at compile time.
+///
+/// See [`ID::from_name`](crate::id::ID::from_name) for the runtime equivalent.
+///
+/// ```rust
+/// use datalink::{id, id::ID};
+///
+/// const THING: ID = id!("my.namespace/thing");
+///
+/// assert_eq!(THING, ID::from_name("my.namespace/thing"));
+/// ```
+#[macro_export]
+macro_rules! id {
+    ($name:expr) => {{
+        const ID: $crate::id::ID = $crate::id::ID::from_name($name);
+        ID
+    }};
+}
+
+/// Declare a vocabulary of well-known terms in the current module.
+///
+/// Every term gets a zero-sized type implementing [`WellKnown`](crate::well_known::WellKnown) and `Data`
+/// (providing its name as value), and a constant of that type.
+/// If a target type is given like `PORT: PortType<u16>`, the constant is a typed [`Key`](crate::data::key::Key) instead.
+/// The `id` can be omitted, in which case it's derived from the namespaced name like [`id!`](crate::id!).
+/// Terms declared with `value = none` provide no value at all, like [`NONE`](crate::well_known::NONE).
+///
+/// The vocabulary itself is declared as `VOCABULARY`, so there can only be one per module.
+/// Register it to make its terms available through [`registry`](crate::well_known::registry).
+///
+/// ```rust
+/// mod colors {
+///     datalink::well_known! {
+///         namespace = "example/colors";
+///
+///         /// The colour of something
+///         COLOR: ColorType { name = "color", description = "The colour of something" },
+///         SHADE: ShadeType { id = 0x734BFA09_662B_477C_8B61_7E85B6C40001, name = "shade" },
+///         UNSET: UnsetType { name = "unset", value = none },
+///     }
+/// }
+///
+/// use datalink::id::ID;
+/// use datalink::well_known::{registry, WellKnown};
+///
+/// assert_eq!(colors::ColorType::ID, ID::from_name("example/colors/color"));
+///
+/// registry::register(&colors::VOCABULARY);
+/// let term = registry::lookup(colors::ShadeType::ID).unwrap();
+/// assert_eq!(term.qualified_name(), "example/colors/shade");
+/// assert_eq!(term.to_string(), "SHADE");
+///
+/// use datalink::data::DataExt;
+/// assert_eq!(colors::SHADE.as_str().as_deref(), Some("shade"));
+/// assert!(colors::UNSET.as_str().is_none());
+/// ```
+#[cfg(feature = "well_known")]
+#[macro_export]
+macro_rules! well_known {
+    (
+        namespace = $ns:literal;
+        $(
+            $(#[$meta:meta])*
+            $val:ident: $type:ident $(<$target:ty>)? {
+                $(id = $id:expr,)?
+                name = $name:literal
+                $(, value = $value:ident)?
+                $(, description = $desc:literal)?
+                $(,)?
+            }
+        ),* $(,)?
+    ) => {
+        $(
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            pub struct $type;
+            impl $crate::well_known::WellKnown for $type {
+                const ID: $crate::id::ID = $crate::well_known!(@id $ns, $name $(, $id)?);
+            }
+            $crate::well_known!(@data $type, $name $(, $value)?);
+            $crate::well_known!(@const $val, $type, [$($target)?] $(#[$meta])*);
+        )*
+
+        /// Terms declared in this module
+        pub static VOCABULARY: $crate::well_known::Vocabulary = $crate::well_known::Vocabulary::new(
+            $ns,
+            &[$(
+                $crate::well_known::Term::new(
+                    <$type as $crate::well_known::WellKnown>::ID,
+                    $ns,
+                    $name,
+                    stringify!($val),
+                    $crate::well_known!(@desc $($desc)?),
+                ),
+            )*],
+        );
+    };
+    (@const $val:ident, $type:ident, [] $(#[$meta:meta])*) => {
+        $(#[$meta])*
+        pub const $val: $type = $type;
+    };
+    (@const $val:ident, $type:ident, [$target:ty] $(#[$meta:meta])*) => {
+        $(#[$meta])*
+        pub const $val: $crate::data::key::Key<
+            { <$type as $crate::well_known::WellKnown>::ID.get() },
+            $target,
+            $type,
+        > = $crate::data::key::Key::new($type);
+    };
+    (@data $type:ident, $name:literal) => {
+        $crate::impl_data!(
+            $type,
+            id = <Self as $crate::well_known::WellKnown>::ID.into_raw(),
+            value = $name
+        );
+    };
+    (@data $type:ident, $name:literal, none) => {
+        $crate::impl_data!(
+            $type,
+            id = <Self as $crate::well_known::WellKnown>::ID.into_raw()
+        );
+    };
+    (@id $ns:literal, $name:literal) => {
+        $crate::id::ID::from_name(concat!($ns, "/", $name))
+    };
+    (@id $ns:literal, $name:literal, $id:expr) => {
+        match ::core::num::NonZeroU128::new($id) {
+            Some(id) => $crate::id::ID::from_raw(id),
+            None => panic!("ID of a well-known term must not be zero"),
+        }
+    };
+    (@desc) => {
+        None
+    };
+    (@desc $desc:literal) => {
+        Some($desc)
+    };
+}
+
 /// Implement the `Data` trait for a type.
 ///
 /// # Examples
@@ -1,11 +1,15 @@
-use std::borrow::Borrow;
-
 #[cfg(feature = "unique")]
 use crate::data::unique::Unique;
+use std::ops::{Bound, RangeBounds};
+
 use crate::{id::ID, BoxedData, Data};
 use filters::{And, FalsyDefault, Filter, Not, Optimizable, Or, TextFilter, TruthyDefault};
 
+use super::kind::ValueKind;
 use super::linkfilter::LinkFilter;
+use super::numeric::{FirstNumber, Number};
+use super::provide::{Erased, Provide, Typed};
+use super::text::Matcher as TextMatcher;
 
 #[derive(Default, Debug)]
 #[non_exhaustive]
@@ -16,10 +20,39 @@ pub enum DataFilter {
     And(And<DataFilter>),
     Not(Box<Not<DataFilter>>),
     Text(TextFilter),
+    /// Provides a string starting with this prefix
+    Prefix(String),
+    /// Provides a string in the range
+    StrRange(Bound<String>, Bound<String>),
     Unique,
     Id(ID),
     NotId(ID),
     Linked(Box<LinkFilter>),
+    /// Has at least `n` links matching the filter
+    AtLeast(usize, Box<LinkFilter>),
+    /// Has at most `n` links matching the filter
+    AtMost(usize, Box<LinkFilter>),
+    /// Has exactly `n` links matching the filter
+    Exactly(usize, Box<LinkFilter>),
+    /// Every link matches the filter, true if there are no links
+    AllLinked(Box<LinkFilter>),
+    /// A node matching the `DataFilter` is reachable in at most `max_depth` steps
+    /// along links matching the `LinkFilter`
+    Reachable(usize, Box<LinkFilter>, Box<DataFilter>),
+    /// Provides a number less than the given one
+    Lt(Number),
+    /// Provides a number less than or equal to the given one
+    Le(Number),
+    /// Provides a number greater than the given one
+    Gt(Number),
+    /// Provides a number greater than or equal to the given one
+    Ge(Number),
+    /// Provides a number in the inclusive range
+    Between(Number, Number),
+    /// Provides a number equal to the given one, regardless of its type
+    EqNum(Number),
+    /// Provides a value of the given kind
+    Is(ValueKind),
     None,
 }
 
@@ -39,6 +72,41 @@ impl DataFilter {
     pub fn text(f: impl Into<TextFilter>) -> Self {
         Self::Text(f.into())
     }
+    /// Matches data providing exactly the string `s`
+    ///
+    /// Short for an exact [`text`](DataFilter::text) filter. Maps look keys matched by this up
+    /// instead of scanning all entries.
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use datalink::data::BoxedData;
+    /// use datalink::prelude::*;
+    /// use datalink::query::{DataFilter, LinkFilter, Query};
+    ///
+    /// let config = HashMap::from([("port", 8080u16), ("workers", 4)]);
+    /// let query = Query::new(LinkFilter::key(DataFilter::exact("port")));
+    ///
+    /// let mut links: Vec<(BoxedData, BoxedData)> = Vec::new();
+    /// config.query_links(&mut links, &query).unwrap();
+    /// assert_eq!(links.len(), 1);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn exact(s: impl Into<String>) -> Self {
+        Self::Text(TextFilter::Exact(s.into()))
+    }
+    #[inline]
+    #[must_use]
+    pub fn prefix(s: impl Into<String>) -> Self {
+        Self::Prefix(s.into())
+    }
+    /// Matches strings in `range`, compared like [`str`]
+    #[inline]
+    #[must_use]
+    pub fn str_range<S: AsRef<str>>(range: impl RangeBounds<S>) -> Self {
+        let owned = |b: Bound<&S>| b.map(|s| s.as_ref().to_owned());
+        Self::StrRange(owned(range.start_bound()), owned(range.end_bound()))
+    }
     #[inline]
     #[must_use]
     pub const fn unique() -> Self {
@@ -59,6 +127,137 @@ impl DataFilter {
     pub fn linked(filter: impl Into<LinkFilter>) -> Self {
         Self::Linked(Box::new(filter.into()))
     }
+    #[inline]
+    #[must_use]
+    pub fn at_least(n: usize, filter: impl Into<LinkFilter>) -> Self {
+        Self::AtLeast(n, Box::new(filter.into()))
+    }
+    #[inline]
+    #[must_use]
+    pub fn at_most(n: usize, filter: impl Into<LinkFilter>) -> Self {
+        Self::AtMost(n, Box::new(filter.into()))
+    }
+    /// Matches data with exactly `n` links matching `filter`
+    ///
+    /// ```rust
+    /// use datalink::query::{DataFilter, Filter, LinkFilter};
+    ///
+    /// let empty = DataFilter::exactly(0, LinkFilter::any());
+    ///
+    /// assert!(empty.matches(&Vec::<u8>::new()));
+    /// assert!(!empty.matches(&vec![1u8]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn exactly(n: usize, filter: impl Into<LinkFilter>) -> Self {
+        Self::Exactly(n, Box::new(filter.into()))
+    }
+    #[inline]
+    #[must_use]
+    pub fn all_linked(filter: impl Into<LinkFilter>) -> Self {
+        Self::AllLinked(Box::new(filter.into()))
+    }
+    /// Matches data from which a node matching `target` can be reached
+    ///
+    /// Only links matching `edge` are followed, up to `max_depth` links deep.
+    /// Data with an ID is visited at most once.
+    ///
+    /// ```rust
+    /// use datalink::query::{DataFilter, Filter, LinkFilter};
+    ///
+    /// let nested = vec![vec![1u8], vec![2u8, 42u8]];
+    /// let answer = DataFilter::reachable(2, LinkFilter::any(), DataFilter::eq_num(42u8));
+    ///
+    /// assert!(answer.matches(&nested));
+    /// assert!(!answer.matches(&vec![nested]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn reachable(
+        max_depth: usize,
+        edge: impl Into<LinkFilter>,
+        target: impl Into<DataFilter>,
+    ) -> Self {
+        Self::Reachable(max_depth, Box::new(edge.into()), Box::new(target.into()))
+    }
+    #[inline]
+    #[must_use]
+    pub fn lt(n: impl Into<Number>) -> Self {
+        Self::Lt(n.into())
+    }
+    #[inline]
+    #[must_use]
+    pub fn le(n: impl Into<Number>) -> Self {
+        Self::Le(n.into())
+    }
+    #[inline]
+    #[must_use]
+    pub fn gt(n: impl Into<Number>) -> Self {
+        Self::Gt(n.into())
+    }
+    #[inline]
+    #[must_use]
+    pub fn ge(n: impl Into<Number>) -> Self {
+        Self::Ge(n.into())
+    }
+    /// Matches numbers from `min` to `max`, both inclusive
+    ///
+    /// ```rust
+    /// use datalink::query::{DataFilter, Filter};
+    ///
+    /// let port = DataFilter::between(1024u16, 65535u16);
+    ///
+    /// assert!(port.matches(&8080u16));
+    /// assert!(port.matches(&8080.0f64));
+    /// assert!(!port.matches(&80i32));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn between(min: impl Into<Number>, max: impl Into<Number>) -> Self {
+        Self::Between(min.into(), max.into())
+    }
+    #[inline]
+    #[must_use]
+    pub fn eq_num(n: impl Into<Number>) -> Self {
+        Self::EqNum(n.into())
+    }
+    #[inline]
+    #[must_use]
+    pub const fn is_bool() -> Self {
+        Self::Is(ValueKind::Bool)
+    }
+    #[inline]
+    #[must_use]
+    pub const fn is_number() -> Self {
+        Self::Is(ValueKind::Number)
+    }
+    #[inline]
+    #[must_use]
+    pub const fn is_string() -> Self {
+        Self::Is(ValueKind::String)
+    }
+    #[inline]
+    #[must_use]
+    pub const fn is_bytes() -> Self {
+        Self::Is(ValueKind::Bytes)
+    }
+    #[inline]
+    #[must_use]
+    pub const fn is_null() -> Self {
+        Self::Is(ValueKind::Null)
+    }
+    /// Has at least one link
+    #[inline]
+    #[must_use]
+    pub fn has_links() -> Self {
+        Self::linked(LinkFilter::Any)
+    }
+    /// Has no links
+    #[inline]
+    #[must_use]
+    pub fn is_leaf() -> Self {
+        !Self::has_links()
+    }
     #[cfg(feature = "unique")]
     #[inline]
     #[must_use]
@@ -97,93 +296,149 @@ impl DataFilter {
 impl<D: Data + ?Sized> Filter<D> for DataFilter {
     #[inline]
     fn matches(&self, d: &D) -> bool {
+        self.eval::<D, Erased>(d)
+    }
+}
+
+impl DataFilter {
+    /// Like [`Filter::matches`], but values are requested through [`Data::provide_requested`]
+    ///
+    /// This avoids the dynamic dispatch of [`Data::provide_value`] for data that
+    /// supports typed requests. Links are still matched through [`Filter::matches`].
+    ///
+    /// ```rust
+    /// use datalink::query::{DataFilter, Filter};
+    ///
+    /// let filter = DataFilter::text("needle");
+    ///
+    /// assert!(filter.matches_typed(&String::from("needle")));
+    /// assert_eq!(filter.matches_typed(&"hay"), filter.matches(&"hay"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn matches_typed<D: Data>(&self, d: &D) -> bool {
+        self.eval::<D, Typed>(d)
+    }
+
+    /// Evaluates the filter, asking for values through `P`
+    pub(super) fn eval<D: Data + ?Sized, P: Provide<D>>(&self, d: &D) -> bool {
         use DataFilter as E;
         match self {
             E::Any => true,
             E::None => false,
-            E::And(and) => and.iter().all(|f| Filter::<D>::matches(f, d)),
-            E::Or(or) => or.iter().any(|f| Filter::<D>::matches(f, d)),
+            E::And(and) => and.iter().all(|f| f.eval::<D, P>(d)),
+            E::Or(or) => or.iter().any(|f| f.eval::<D, P>(d)),
             E::Id(id) => d.get_id().is_some_and(|ref i| i == id),
             E::NotId(id) => !d.get_id().is_some_and(|ref i| i == id),
-            E::Not(f) => f.matches(d),
+            E::Not(f) => !f.0.eval::<D, P>(d),
             E::Unique => d.get_id().is_some(),
-            E::Linked(f) => {
-                struct Searcher<'a>(bool, &'a LinkFilter);
-                impl crate::links::Links for Searcher<'_> {
-                    #[inline]
-                    fn push(
-                        &mut self,
-                        target: BoxedData,
-                        key: Option<BoxedData>,
-                    ) -> crate::links::Result {
-                        if let Some(key) = key {
-                            self.push_keyed(target, key)
-                        } else {
-                            self.push_unkeyed(target)
-                        }
-                    }
-                    #[inline]
-                    fn push_keyed(
-                        &mut self,
-                        target: BoxedData,
-                        key: BoxedData,
-                    ) -> crate::links::Result {
-                        if self.1.matches_owned((key, target)) {
-                            self.0 = true;
-                            crate::links::BREAK
-                        } else {
-                            crate::links::CONTINUE
-                        }
-                    }
-                    #[inline]
-                    fn push_unkeyed(&mut self, target: BoxedData) -> crate::links::Result {
-                        if Filter::<BoxedData>::matches_owned(self.1, target) {
-                            self.0 = true;
-                            crate::links::BREAK
-                        } else {
-                            crate::links::CONTINUE
-                        }
-                    }
-                }
-                let mut searcher = Searcher(false, f);
-                let _ = d.borrow().provide_links(&mut searcher);
-                searcher.0
+            E::Linked(f) => count_links(d, f, 1, false).matched >= 1,
+            E::AtLeast(n, f) => count_links(d, f, *n, false).matched >= *n,
+            E::AtMost(n, f) => count_links(d, f, n.saturating_add(1), false).matched <= *n,
+            E::Exactly(n, f) => count_links(d, f, n.saturating_add(1), false).matched == *n,
+            E::AllLinked(f) => !count_links(d, f, usize::MAX, true).missed,
+            E::Reachable(max_depth, edge, target) => {
+                super::reach::reachable(d, *max_depth, edge, target)
             }
-            E::Text(f) => {
-                use crate::rr::{Receiver, Request};
+            E::Lt(n) => matches_number::<D, P>(d, |v| v < *n),
+            E::Le(n) => matches_number::<D, P>(d, |v| v <= *n),
+            E::Gt(n) => matches_number::<D, P>(d, |v| v > *n),
+            E::Ge(n) => matches_number::<D, P>(d, |v| v >= *n),
+            E::Between(min, max) => matches_number::<D, P>(d, |v| *min <= v && v <= *max),
+            E::EqNum(n) => matches_number::<D, P>(d, |v| v == *n),
+            E::Is(kind) => kind.provided_with::<D, P>(d),
+            E::Prefix(p) => matches_str::<D, P>(d, |v| v.starts_with(p.as_str())),
+            E::StrRange(start, end) => {
+                let range = (
+                    start.as_ref().map(String::as_str),
+                    end.as_ref().map(String::as_str),
+                );
+                matches_str::<D, P>(d, |v| RangeBounds::<str>::contains(&range, v))
+            }
+            E::Text(f) => matches_str::<D, P>(d, |v| f.matches(v)),
+        }
+    }
+}
 
-                enum Matcher<'a> {
-                    Searching(&'a TextFilter),
-                    Found,
-                }
-                impl Receiver for Matcher<'_> {
-                    #[inline]
-                    fn str(&mut self, value: &str) {
-                        if let Matcher::Searching(f) = self {
-                            if f.matches(value) {
-                                *self = Matcher::Found;
-                            }
-                        }
-                    }
-                    #[inline]
-                    fn accepting() -> impl crate::rr::typeset::TypeSet + 'static {
-                        crate::rr::typeset::STRING_LIKE
-                    }
-                }
+/// Counts the links of `d` matching `filter`
+///
+/// Stops once `limit` links matched or, with `stop_on_miss`, at the first link that did not.
+fn count_links<'a>(
+    d: &(impl Data + ?Sized),
+    filter: &'a LinkFilter,
+    limit: usize,
+    stop_on_miss: bool,
+) -> LinkCounter<'a> {
+    let mut counter = LinkCounter {
+        filter,
+        limit,
+        stop_on_miss,
+        matched: 0,
+        missed: false,
+    };
+    if limit > 0 {
+        let _ = d.provide_links(&mut counter);
+    }
+    counter
+}
 
-                let mut m = Matcher::Searching(f);
+struct LinkCounter<'a> {
+    filter: &'a LinkFilter,
+    limit: usize,
+    stop_on_miss: bool,
+    matched: usize,
+    missed: bool,
+}
 
-                // TODO: Optimized path
-                // let mut request = Request::<Matcher>::new(&mut m);
-                // if d.provide_requested(&mut request).was_provided() {
-                //     return matches!(m, Matcher::Found);
-                // }
+impl LinkCounter<'_> {
+    #[inline]
+    fn count(&mut self, matched: bool) -> crate::links::Result {
+        if matched {
+            self.matched += 1;
+        } else {
+            self.missed = true;
+        }
+        if self.matched >= self.limit || (self.missed && self.stop_on_miss) {
+            crate::links::BREAK
+        } else {
+            crate::links::CONTINUE
+        }
+    }
+}
 
-                d.provide_value(&mut Request::new_erased(&mut m));
-                matches!(m, Matcher::Found)
-            }
+impl crate::links::Links for LinkCounter<'_> {
+    #[inline]
+    fn push(&mut self, target: BoxedData, key: Option<BoxedData>) -> crate::links::Result {
+        if let Some(key) = key {
+            self.push_keyed(target, key)
+        } else {
+            self.push_unkeyed(target)
         }
     }
+    #[inline]
+    fn push_keyed(&mut self, target: BoxedData, key: BoxedData) -> crate::links::Result {
+        let matched = self.filter.matches_owned((key, target));
+        self.count(matched)
+    }
+    #[inline]
+    fn push_unkeyed(&mut self, target: BoxedData) -> crate::links::Result {
+        let matched = Filter::<BoxedData>::matches_owned(self.filter, target);
+        self.count(matched)
+    }
+}
+
+/// Whether any string provided by `d` satisfies `predicate`
+fn matches_str<D: ?Sized, P: Provide<D>>(d: &D, predicate: impl Fn(&str) -> bool) -> bool {
+    let mut matcher = TextMatcher::new(predicate);
+    P::provide(d, &mut matcher);
+    matcher.matched()
+}
+
+/// Whether the first number provided by `d` satisfies `predicate`
+fn matches_number<D: ?Sized, P: Provide<D>>(d: &D, predicate: impl Fn(Number) -> bool) -> bool {
+    let mut first = FirstNumber::default();
+    P::provide(d, &mut first);
+    first.0.is_some_and(predicate)
 }
 
 /// `DataFilter` used to filter Links in the form of `(key, target)`.
@@ -205,6 +460,21 @@ impl Optimizable for DataFilter {
             E::Or(f) => f.as_bool(),
             E::Not(f) => f.as_bool(),
             E::Text(f) => f.as_bool(),
+            // Nothing compares to NaN
+            E::Lt(n) | E::Le(n) | E::Gt(n) | E::Ge(n) | E::EqNum(n) if n.is_nan() => Some(false),
+            E::AtLeast(0, _) => Some(true),
+            E::Reachable(0, _, _) => Some(false),
+            E::Reachable(_, edge, target)
+                if edge.as_bool() == Some(false) || target.as_bool() == Some(false) =>
+            {
+                Some(false)
+            }
+            E::AllLinked(f) if f.as_bool() == Some(true) => Some(true),
+            E::AtMost(_, f) | E::Exactly(0, f) if f.as_bool() == Some(false) => Some(true),
+            E::Linked(f) | E::AtLeast(_, f) if f.as_bool() == Some(false) => Some(false),
+            E::Between(min, max) if min.partial_cmp(max).is_none_or(std::cmp::Ordering::is_gt) => {
+                Some(false)
+            }
             _ => None,
         }
     }
@@ -217,6 +487,16 @@ impl Optimizable for DataFilter {
             E::Or(f) => f.optimize(),
             E::Not(f) => f.optimize(),
             E::Text(f) => f.optimize(),
+            E::Linked(f)
+            | E::AtLeast(_, f)
+            | E::AtMost(_, f)
+            | E::Exactly(_, f)
+            | E::AllLinked(f) => f.optimize(),
+            E::Reachable(_, edge, target) => {
+                edge.optimize();
+                target.optimize();
+            }
+            E::Between(min, max) if min == max => *self = E::EqNum(*min),
             _ => {}
         }
         match self.as_bool() {
@@ -262,3 +542,57 @@ impl std::ops::Not for DataFilter {
         Self::Not(Box::new(Not(self)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cardinality() {
+        let v = vec![1u8, 2, 3];
+        assert!(DataFilter::at_least(3, LinkFilter::Any).matches(&v));
+        assert!(!DataFilter::at_least(4, LinkFilter::Any).matches(&v));
+        assert!(DataFilter::at_most(3, LinkFilter::Any).matches(&v));
+        assert!(!DataFilter::at_most(2, LinkFilter::Any).matches(&v));
+        assert!(DataFilter::exactly(3, LinkFilter::Any).matches(&v));
+        assert!(!DataFilter::exactly(2, LinkFilter::Any).matches(&v));
+        assert!(DataFilter::exactly(1, LinkFilter::target(DataFilter::gt(2u8))).matches(&v));
+        assert!(DataFilter::at_least(0, LinkFilter::None).matches(&v));
+    }
+
+    #[test]
+    fn all_linked() {
+        let v = vec![1u8, 2, 3];
+        assert!(DataFilter::all_linked(LinkFilter::target(DataFilter::lt(4u8))).matches(&v));
+        assert!(!DataFilter::all_linked(LinkFilter::target(DataFilter::lt(3u8))).matches(&v));
+        assert!(DataFilter::all_linked(LinkFilter::None).matches(&Vec::<u8>::new()));
+    }
+
+    #[test]
+    fn stops_early() {
+        #[derive(Debug)]
+        struct Endless;
+        impl Data for Endless {
+            fn provide_links(
+                &self,
+                links: &mut dyn crate::links::Links,
+            ) -> Result<(), crate::links::LinkError> {
+                while links.push(Box::new(1u8), None)?.is_continue() {}
+                Ok(())
+            }
+        }
+        assert!(DataFilter::at_least(5, LinkFilter::Any).matches(&Endless));
+        assert!(!DataFilter::at_most(5, LinkFilter::Any).matches(&Endless));
+        assert!(!DataFilter::all_linked(LinkFilter::None).matches(&Endless));
+    }
+
+    #[test]
+    fn optimize() {
+        let mut f = DataFilter::at_least(0, LinkFilter::None);
+        f.optimize();
+        assert!(matches!(f, DataFilter::Any));
+        let mut f = DataFilter::linked(LinkFilter::None);
+        f.optimize();
+        assert!(matches!(f, DataFilter::None));
+    }
+}
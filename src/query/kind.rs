@@ -0,0 +1,190 @@
+use std::any::Any;
+use std::marker::PhantomData;
+
+use super::provide::{Erased, Provide};
+use crate::rr::{meta, typeset, Receiver, TypeSet};
+use crate::Data;
+
+/// Kind of value a datum can provide, used by [`DataFilter::Is`](super::DataFilter::Is)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ValueKind {
+    Bool,
+    /// Any integer or float
+    Number,
+    String,
+    Bytes,
+    /// `IsNull` or `IsNone`
+    Null,
+}
+
+impl ValueKind {
+    /// Whether `data` provides a value of this kind
+    ///
+    /// Only the types of the kind are requested and no values are stored.
+    #[inline]
+    pub fn provided_by(self, data: &(impl Data + ?Sized)) -> bool {
+        self.provided_with::<_, Erased>(data)
+    }
+
+    #[inline]
+    pub(super) fn provided_with<D: ?Sized, P: Provide<D>>(self, data: &D) -> bool {
+        match self {
+            ValueKind::Bool => detect::<_, P, typeset::Only<bool>>(data),
+            ValueKind::Number => detect::<_, P, typeset::Numeric>(data),
+            ValueKind::String => detect::<_, P, typeset::StringLike>(data),
+            ValueKind::Bytes => detect::<_, P, typeset::BytesLike>(data),
+            ValueKind::Null => detect::<_, P, Null>(data),
+        }
+    }
+}
+
+type Null = typeset::AnyOf<(meta::IsNull, meta::IsNone)>;
+
+fn detect<D: ?Sized, P: Provide<D>, S: TypeSet + Default + 'static>(data: &D) -> bool {
+    let mut detector = Detector::<S>(false, PhantomData);
+    P::provide(data, &mut detector);
+    detector.0
+}
+
+/// Receiver noting whether any value of the types in `S` was provided
+///
+/// Requests only pass on values of accepted types, so every call is a hit.
+struct Detector<S>(bool, PhantomData<S>);
+
+impl<S: TypeSet + Default + 'static> Receiver for Detector<S> {
+    #[inline]
+    fn bool(&mut self, _value: bool) {
+        self.0 = true;
+    }
+    #[inline]
+    fn i8(&mut self, _value: i8) {
+        self.0 = true;
+    }
+    #[inline]
+    fn u8(&mut self, _value: u8) {
+        self.0 = true;
+    }
+    #[inline]
+    fn i16(&mut self, _value: i16) {
+        self.0 = true;
+    }
+    #[inline]
+    fn u16(&mut self, _value: u16) {
+        self.0 = true;
+    }
+    #[inline]
+    fn i32(&mut self, _value: i32) {
+        self.0 = true;
+    }
+    #[inline]
+    fn u32(&mut self, _value: u32) {
+        self.0 = true;
+    }
+    #[inline]
+    fn i64(&mut self, _value: i64) {
+        self.0 = true;
+    }
+    #[inline]
+    fn u64(&mut self, _value: u64) {
+        self.0 = true;
+    }
+    #[inline]
+    fn i128(&mut self, _value: i128) {
+        self.0 = true;
+    }
+    #[inline]
+    fn u128(&mut self, _value: u128) {
+        self.0 = true;
+    }
+    #[inline]
+    fn f32(&mut self, _value: f32) {
+        self.0 = true;
+    }
+    #[inline]
+    fn f64(&mut self, _value: f64) {
+        self.0 = true;
+    }
+    #[inline]
+    fn char(&mut self, _value: char) {
+        self.0 = true;
+    }
+    #[inline]
+    fn str(&mut self, _value: &str) {
+        self.0 = true;
+    }
+    #[inline]
+    fn str_owned(&mut self, _value: String) {
+        self.0 = true;
+    }
+    #[inline]
+    fn bytes(&mut self, _value: &[u8]) {
+        self.0 = true;
+    }
+    #[inline]
+    fn bytes_owned(&mut self, _value: Vec<u8>) {
+        self.0 = true;
+    }
+    #[inline]
+    fn other_ref(&mut self, _value: &dyn Any) {
+        self.0 = true;
+    }
+    #[inline]
+    fn other_boxed(&mut self, _value: Box<dyn Any>) {
+        self.0 = true;
+    }
+    #[inline]
+    fn accepting() -> impl TypeSet + 'static {
+        S::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::query::{DataFilter, Filter};
+
+    #[test]
+    fn kinds() {
+        assert!(DataFilter::is_bool().matches(&true));
+        assert!(!DataFilter::is_bool().matches(&1u8));
+        assert!(DataFilter::is_number().matches(&1u8));
+        assert!(DataFilter::is_number().matches(&1.5f32));
+        assert!(!DataFilter::is_number().matches(&"1"));
+        assert!(DataFilter::is_string().matches(&"1"));
+        assert!(DataFilter::is_string().matches(&String::from("1")));
+        assert!(DataFilter::is_bytes().matches(&[1u8].as_slice()));
+        assert!(DataFilter::is_null().matches(&None::<u8>));
+        assert!(!DataFilter::is_null().matches(&Some(1u8)));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn links() {
+        assert!(DataFilter::has_links().matches(&vec![1u8]));
+        assert!(!DataFilter::has_links().matches(&Vec::<u8>::new()));
+        assert!(DataFilter::is_leaf().matches(&1u8));
+        assert!(!DataFilter::is_leaf().matches(&vec![1u8]));
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn json() {
+        use crate::data::BoxedData;
+        use crate::data::DataExt;
+        use crate::query::{LinkFilter, Query};
+
+        let json = serde_json::json!({"a": 1, "b": null, "c": "x", "d": [1.5]});
+
+        let numbers = Query::new(LinkFilter::target(DataFilter::is_number()));
+        let found: Vec<BoxedData> = json.query(&numbers).unwrap();
+        assert_eq!(found.len(), 1);
+
+        let nulls = Query::new(LinkFilter::target(DataFilter::is_null()));
+        let found: Vec<BoxedData> = json.query(&nulls).unwrap();
+        assert_eq!(found.len(), 1);
+
+        let leaves = Query::new(LinkFilter::target(DataFilter::is_leaf()));
+        let found: Vec<BoxedData> = json.query(&leaves).unwrap();
+        assert_eq!(found.len(), 3);
+    }
+}
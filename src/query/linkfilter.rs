@@ -2,6 +2,7 @@ use crate::links::Link;
 use filters::{And, FalsyDefault, Filter, Not, Optimizable, Or, TruthyDefault};
 
 use super::datafilter::DataFilter;
+use super::provide::{Erased, Provide, Typed};
 
 #[derive(Default, Debug)]
 #[non_exhaustive]
@@ -60,22 +61,50 @@ impl LinkFilter {
         }
     }
 }
-impl<L: Link + ?Sized> Filter<L> for LinkFilter {
+impl LinkFilter {
+    /// Like [`Filter::matches`], with key and target matched by [`DataFilter::matches_typed`]
     #[inline]
-    fn matches(&self, l: &L) -> bool {
+    #[must_use]
+    pub fn matches_typed<L>(&self, l: &L) -> bool
+    where
+        L: Link + ?Sized,
+        L::Key: Sized,
+        L::Target: Sized,
+    {
+        self.eval::<L, Typed>(l)
+    }
+
+    /// Evaluates the filter, matching keys and targets with values provided through `P`
+    fn eval<L, P>(&self, l: &L) -> bool
+    where
+        L: Link + ?Sized,
+        P: Provide<L::Key> + Provide<L::Target>,
+    {
         use LinkFilter as E;
         match self {
             E::Any => true,
             E::None => false,
-            E::Not(f) => !f.matches(l),
-            E::And(f) => f.matches(l),
-            E::Or(f) => f.matches(l),
-            E::Key(f) => l.key().is_some_and(|k| f.matches(k)),
-            E::Target(f) => f.matches(l.target()),
+            E::Not(f) => !f.0.eval::<L, P>(l),
+            E::And(f) => f.iter().all(|f| f.eval::<L, P>(l)),
+            E::Or(f) => f.iter().any(|f| f.eval::<L, P>(l)),
+            E::Key(f) => l.key().is_some_and(|k| f.eval::<L::Key, P>(k)),
+            E::Target(f) => f.eval::<L::Target, P>(l.target()),
         }
     }
 }
 
+/// Asks for values through [`Data::provide_value`](crate::Data::provide_value), as `L` may
+/// link unsized data like the `dyn Data` of boxed links, which can't take typed requests
+///
+/// Code holding sized links calls [`LinkFilter::matches_typed`] instead, like the
+/// `query_links` of maps and lists. `benches/matches.rs` compares both.
+impl<L: Link + ?Sized> Filter<L> for LinkFilter {
+    #[inline]
+    fn matches(&self, l: &L) -> bool {
+        self.eval::<L, Erased>(l)
+    }
+}
+
 impl Optimizable for LinkFilter {
     #[inline]
     fn as_bool(&self) -> Option<bool> {
@@ -150,3 +179,21 @@ impl std::ops::Not for LinkFilter {
         Self::Not(Box::new(Not(self)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn not() {
+        let small = !LinkFilter::target(DataFilter::gt(4u8));
+        assert!(small.matches(&3u8));
+        assert!(!small.matches(&5u8));
+        assert_eq!(small.matches(&3u8), small.matches_typed(&3u8));
+        assert_eq!(small.matches(&5u8), small.matches_typed(&5u8));
+
+        let unkeyed = !LinkFilter::key(DataFilter::text("a"));
+        assert!(unkeyed.matches(&1u8));
+        assert!(!unkeyed.matches(&("a", 1u8)));
+    }
+}
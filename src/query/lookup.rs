@@ -0,0 +1,139 @@
+use std::ops::Bound;
+
+use filters::TextFilter;
+
+use super::{DataFilter, LinkFilter};
+
+/// Keys a link must have to match a filter, as far as the filter tells
+///
+/// Maps use this to look keys up instead of scanning all entries. The candidates still
+/// have to be checked against the full filter.
+#[derive(Debug, PartialEq)]
+pub(crate) enum KeyHint<'a> {
+    /// One of these keys, sorted and without duplicates
+    Exact(Vec<&'a str>),
+    Prefix(&'a str),
+    Range(Bound<&'a str>, Bound<&'a str>),
+}
+
+impl KeyHint<'_> {
+    #[inline]
+    pub(crate) const fn is_exact(&self) -> bool {
+        matches!(self, KeyHint::Exact(_))
+    }
+
+    /// Merges exact hints, other hints can't be merged
+    fn union(self, other: Self) -> Option<Self> {
+        match (self, other) {
+            (KeyHint::Exact(mut a), KeyHint::Exact(b)) => {
+                a.extend(b);
+                a.sort_unstable();
+                a.dedup();
+                Some(KeyHint::Exact(a))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl LinkFilter {
+    /// Keys of the links matching this filter, see [`KeyHint`]
+    #[inline]
+    pub(crate) fn key_hint(&self) -> Option<KeyHint<'_>> {
+        match self {
+            LinkFilter::Key(f) => f.str_hint(),
+            LinkFilter::And(and) => and.iter().find_map(LinkFilter::key_hint),
+            LinkFilter::Or(or) => union(or.iter().map(LinkFilter::key_hint)),
+            _ => None,
+        }
+    }
+}
+
+impl DataFilter {
+    /// Strings matching this filter, see [`KeyHint`]
+    #[inline]
+    pub(crate) fn str_hint(&self) -> Option<KeyHint<'_>> {
+        match self {
+            DataFilter::Text(TextFilter::Exact(s)) => Some(KeyHint::Exact(vec![s])),
+            DataFilter::Prefix(p) => Some(KeyHint::Prefix(p)),
+            DataFilter::StrRange(start, end) => Some(KeyHint::Range(
+                start.as_ref().map(String::as_str),
+                end.as_ref().map(String::as_str),
+            )),
+            DataFilter::And(and) => and.iter().find_map(DataFilter::str_hint),
+            DataFilter::Or(or) => union(or.iter().map(DataFilter::str_hint)),
+            _ => None,
+        }
+    }
+}
+
+/// Whether `range` contains nothing, which `BTreeMap::range` would panic on
+pub(crate) fn is_empty_range<T: Ord>((start, end): &(Bound<T>, Bound<T>)) -> bool {
+    use Bound::{Excluded, Included};
+    match (start, end) {
+        (Included(s), Included(e)) => s > e,
+        (Included(s) | Excluded(s), Excluded(e)) | (Excluded(s), Included(e)) => s >= e,
+        _ => false,
+    }
+}
+
+fn union<'a>(mut hints: impl Iterator<Item = Option<KeyHint<'a>>>) -> Option<KeyHint<'a>> {
+    let first = hints.next()??;
+    hints.try_fold(first, |acc, hint| acc.union(hint?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact() {
+        let f = LinkFilter::key(DataFilter::exact("b") | DataFilter::exact("a"))
+            | LinkFilter::key(DataFilter::exact("a"));
+        assert_eq!(f.key_hint(), Some(KeyHint::Exact(vec!["a", "b"])));
+
+        let f = LinkFilter::key(DataFilter::exact("a")) & LinkFilter::target(DataFilter::gt(1u8));
+        assert_eq!(f.key_hint(), Some(KeyHint::Exact(vec!["a"])));
+
+        let f = LinkFilter::key(DataFilter::text("b") | DataFilter::exact("a"));
+        assert_eq!(f.key_hint(), Some(KeyHint::Exact(vec!["a", "b"])));
+    }
+
+    #[test]
+    fn unhinted() {
+        let f = LinkFilter::key(DataFilter::exact("a")) | LinkFilter::target(DataFilter::Any);
+        assert_eq!(f.key_hint(), None);
+        let f = LinkFilter::key(DataFilter::exact("a")) | LinkFilter::key(DataFilter::prefix("b"));
+        assert_eq!(f.key_hint(), None);
+        assert_eq!(LinkFilter::key(!DataFilter::exact("a")).key_hint(), None);
+        assert_eq!(LinkFilter::target(DataFilter::exact("a")).key_hint(), None);
+    }
+
+    #[test]
+    fn ranges() {
+        let f = LinkFilter::key(DataFilter::str_range("a".."c"));
+        assert_eq!(
+            f.key_hint(),
+            Some(KeyHint::Range(Bound::Included("a"), Bound::Excluded("c")))
+        );
+        let f = LinkFilter::key(DataFilter::prefix("a"));
+        assert_eq!(f.key_hint(), Some(KeyHint::Prefix("a")));
+
+        assert!(is_empty_range(&(
+            Bound::Included("b"),
+            Bound::Included("a")
+        )));
+        assert!(is_empty_range(&(
+            Bound::Excluded("a"),
+            Bound::Excluded("a")
+        )));
+        assert!(!is_empty_range(&(
+            Bound::Included("a"),
+            Bound::Included("a")
+        )));
+        assert!(!is_empty_range(&(
+            Bound::Excluded("b"),
+            Bound::<&str>::Unbounded
+        )));
+    }
+}
@@ -1,13 +1,30 @@
 use std::num::NonZeroUsize;
+use std::sync::Arc;
 
 pub use filters::{Filter, Optimizable};
 
 mod datafilter;
+mod kind;
 mod linkfilter;
+mod lookup;
+pub mod numeric;
+pub(crate) mod path;
+mod plan;
+mod provide;
+mod reach;
+mod selection;
+pub mod sql;
+mod text;
 pub use datafilter::DataFilter;
+pub use kind::ValueKind;
 pub use linkfilter::LinkFilter;
+pub(crate) use lookup::{is_empty_range, KeyHint};
+pub use numeric::Number;
+pub use plan::Plan;
+pub use selection::{Projected, Selection, SelectionError};
 
-use crate::links::Link;
+use crate::data::{BoxedData, Data};
+use crate::links::{Link, LinkError, Links};
 
 pub mod prelude {
     pub use super::DataFilter as Data;
@@ -23,6 +40,9 @@ pub struct Query {
     /// The maximum number of results to return.
     /// `None` means no limit.
     limit: Option<NonZeroUsize>,
+    /// The links to keep below each target.
+    /// `None` means whole targets.
+    selection: Option<Arc<Selection>>,
 }
 
 impl Query {
@@ -32,6 +52,7 @@ impl Query {
         Query {
             filter,
             limit: None,
+            selection: None,
         }
     }
 
@@ -42,6 +63,29 @@ impl Query {
         self
     }
 
+    /// Projects each target to `selection`, see [`Projected`]
+    ///
+    /// Only [`DataExt::query`](crate::data::DataExt::query), its `query_iter` and `aggregate`
+    /// and [`Plan`] project; [`Data::query_links`] pushes whole targets.
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use datalink::data::{BoxedData, DataExt};
+    /// use datalink::query::{LinkFilter, Query, Selection};
+    ///
+    /// let users = vec![HashMap::from([("name", "ada"), ("password", "hunter2")])];
+    /// let query = Query::new(LinkFilter::Any).with_selection(Selection::new().field("name"));
+    ///
+    /// let found: Vec<BoxedData> = users.query(&query).unwrap();
+    /// assert_eq!(found[0].as_items().unwrap().len(), 1);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_selection(mut self, selection: impl Into<Arc<Selection>>) -> Self {
+        self.selection = Some(selection.into());
+        self
+    }
+
     #[inline]
     #[must_use]
     pub fn build(mut self) -> Self {
@@ -69,8 +113,64 @@ impl Query {
             None => usize::MAX,
         }
     }
+
+    /// The links to keep below each target, which data may use to read only those
+    #[inline]
+    #[must_use]
+    pub fn selection(&self) -> Option<&Selection> {
+        self.selection.as_deref()
+    }
+
+    /// Projects a target to the selection, if there is one
+    pub(crate) fn project(&self, target: BoxedData) -> BoxedData {
+        match &self.selection {
+            Some(selection) => Box::new(Projected::new(target, Arc::clone(selection))),
+            None => target,
+        }
+    }
+
+    /// Queries the links of `data` up to the limit, projecting their targets to the selection
+    pub(crate) fn run(
+        &self,
+        data: &(impl Data + ?Sized),
+        links: &mut dyn Links,
+    ) -> Result<(), LinkError> {
+        let mut limited;
+        let links: &mut dyn Links = match self.limit {
+            Some(limit) => {
+                limited = plan::Limited::new(links, limit.get());
+                &mut limited
+            }
+            None => links,
+        };
+        match &self.selection {
+            Some(selection) => data.query_links(
+                &mut selection::Projecting {
+                    inner: links,
+                    selection,
+                },
+                self,
+            ),
+            None => data.query_links(links, self),
+        }
+    }
+}
+
+impl Query {
+    /// Like [`Filter::matches`], see [`DataFilter::matches_typed`]
+    #[inline]
+    #[must_use]
+    pub fn matches_typed<L>(&self, link: &L) -> bool
+    where
+        L: Link + ?Sized,
+        L::Key: Sized,
+        L::Target: Sized,
+    {
+        self.filter.matches_typed(link)
+    }
 }
 
+/// See [`LinkFilter`]'s `Filter` impl for when to use [`Query::matches_typed`] instead
 impl<L: Link + ?Sized> Filter<L> for Query {
     #[inline]
     fn matches(&self, link: &L) -> bool {
@@ -0,0 +1,349 @@
+use std::cmp::Ordering;
+
+use crate::rr::Receiver;
+use crate::value::{Provided, Value, ValueQuery, ValueRequest};
+
+/// A number to compare data against in numeric filters
+///
+/// Comparisons are exact across all integer widths and floats,
+/// so `u64::MAX` is greater than `i64::MAX as f64` and `-1i8` less than `0u128`.
+/// Comparisons with `NaN` are always false.
+#[derive(Debug, Clone, Copy)]
+pub enum Number {
+    Int(i128),
+    UInt(u128),
+    Float(f64),
+}
+
+/// `2^127` and `2^128`, the first floats that are out of range for `i128` and `u128`
+const I128_END: f64 = 170_141_183_460_469_231_731_687_303_715_884_105_728.0;
+const U128_END: f64 = 340_282_366_920_938_463_463_374_607_431_768_211_456.0;
+
+impl Number {
+    #[inline]
+    #[must_use]
+    pub const fn is_nan(&self) -> bool {
+        matches!(self, Number::Float(f) if f.is_nan())
+    }
+
+    /// The same number with integers as `Int` where they fit and integral floats as integers
+    ///
+    /// Equal numbers other than `NaN` have equal normalized representations.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    #[inline]
+    #[must_use]
+    pub(crate) fn normalized(self) -> Self {
+        match self {
+            Number::UInt(u) => i128::try_from(u).map_or(self, Number::Int),
+            Number::Float(f) if f.fract() == 0.0 && (-I128_END..I128_END).contains(&f) => {
+                Number::Int(f as i128)
+            }
+            Number::Float(f) if f.fract() == 0.0 && (0.0..U128_END).contains(&f) => {
+                Number::UInt(f as u128)
+            }
+            _ => self,
+        }
+    }
+}
+
+impl std::hash::Hash for Number {
+    /// Hashes the normalized number, so equal numbers have equal hashes
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        match self.normalized() {
+            Number::Int(i) => i.hash(state),
+            Number::UInt(u) => u.hash(state),
+            Number::Float(f) if f.is_nan() => f64::NAN.to_bits().hash(state),
+            Number::Float(f) => f.to_bits().hash(state),
+        }
+    }
+}
+
+impl PartialEq for Number {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Number {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        use Number::*;
+        match (*self, *other) {
+            (Int(a), Int(b)) => Some(a.cmp(&b)),
+            (UInt(a), UInt(b)) => Some(a.cmp(&b)),
+            (Float(a), Float(b)) => a.partial_cmp(&b),
+            (Int(a), UInt(b)) => Some(cmp_int_uint(a, b)),
+            (UInt(a), Int(b)) => Some(cmp_int_uint(b, a).reverse()),
+            (Int(a), Float(b)) => cmp_int_float(a, b),
+            (UInt(a), Float(b)) => cmp_uint_float(a, b),
+            (Float(a), Int(b)) => cmp_int_float(b, a).map(Ordering::reverse),
+            (Float(a), UInt(b)) => cmp_uint_float(b, a).map(Ordering::reverse),
+        }
+    }
+}
+
+fn cmp_int_uint(a: i128, b: u128) -> Ordering {
+    match u128::try_from(a) {
+        Ok(a) => a.cmp(&b),
+        Err(_) => Ordering::Less,
+    }
+}
+
+/// Compare with the integral part first, then let the fractional part decide
+#[allow(clippy::cast_possible_truncation)]
+fn cmp_int_float(a: i128, b: f64) -> Option<Ordering> {
+    if b.is_nan() {
+        return None;
+    }
+    if b >= I128_END {
+        return Some(Ordering::Less);
+    }
+    if b < -I128_END {
+        return Some(Ordering::Greater);
+    }
+    let trunc = b.trunc();
+    Some(a.cmp(&(trunc as i128)).then(cmp_fract(b, trunc)))
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn cmp_uint_float(a: u128, b: f64) -> Option<Ordering> {
+    if b.is_nan() {
+        return None;
+    }
+    if b >= U128_END {
+        return Some(Ordering::Less);
+    }
+    if b < 0.0 {
+        return Some(Ordering::Greater);
+    }
+    let trunc = b.trunc();
+    Some(a.cmp(&(trunc as u128)).then(cmp_fract(b, trunc)))
+}
+
+/// Ordering of an integer equal to `trunc` relative to `float`
+fn cmp_fract(float: f64, trunc: f64) -> Ordering {
+    if float > trunc {
+        Ordering::Less
+    } else if float < trunc {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+macro_rules! impl_from {
+    ($variant:ident($as:ty): $($ty:ty),*) => {
+        $(
+            impl From<$ty> for Number {
+                #[inline]
+                fn from(value: $ty) -> Self {
+                    Self::$variant(<$as>::from(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(Int(i128): i8, i16, i32, i64, i128);
+impl_from!(UInt(u128): u8, u16, u32, u64, u128);
+impl_from!(Float(f64): f32, f64);
+
+impl From<usize> for Number {
+    #[inline]
+    fn from(value: usize) -> Self {
+        // `usize` is at most 128 bits wide
+        Self::UInt(value as u128)
+    }
+}
+
+impl From<isize> for Number {
+    #[inline]
+    fn from(value: isize) -> Self {
+        Self::Int(value as i128)
+    }
+}
+
+impl TryFrom<&Value> for Number {
+    type Error = ();
+
+    /// The number of a numeric value, booleans are not numbers
+    #[inline]
+    fn try_from(value: &Value) -> Result<Self, ()> {
+        Ok(match *value {
+            Value::U8(v) => v.into(),
+            Value::I8(v) => v.into(),
+            Value::U16(v) => v.into(),
+            Value::I16(v) => v.into(),
+            Value::U32(v) => v.into(),
+            Value::I32(v) => v.into(),
+            Value::U64(v) => v.into(),
+            Value::I64(v) => v.into(),
+            Value::U128(v) => v.into(),
+            Value::I128(v) => v.into(),
+            Value::F32(v) => v.into(),
+            Value::F64(v) => v.into(),
+            _ => return Err(()),
+        })
+    }
+}
+
+impl From<Number> for Value {
+    #[inline]
+    fn from(value: Number) -> Self {
+        match value {
+            Number::Int(i) => Value::I128(i),
+            Number::UInt(u) => Value::U128(u),
+            Number::Float(f) => Value::F64(f),
+        }
+    }
+}
+
+impl crate::Data for Number {
+    #[inline]
+    fn provide_value(&self, request: &mut ValueRequest) {
+        self.provide_requested(request).debug_assert_provided();
+    }
+    #[inline]
+    fn provide_requested<Q: ValueQuery>(&self, request: &mut ValueRequest<Q>) -> impl Provided {
+        match *self {
+            Number::Int(v) => request.provide_i128(v),
+            Number::UInt(v) => request.provide_u128(v),
+            Number::Float(v) => request.provide_f64(v),
+        }
+    }
+}
+
+/// The first number `data` provides
+#[inline]
+pub(crate) fn first_number(data: &dyn crate::Data) -> Option<Number> {
+    let mut first = FirstNumber::default();
+    data.provide_value(&mut crate::rr::Request::new_erased(&mut first));
+    first.0
+}
+
+/// Receiver which keeps the first provided number
+///
+/// Data like `usize` also provides truncated narrower copies after the full value,
+/// so only the first number is kept.
+#[derive(Debug, Default)]
+pub(crate) struct FirstNumber(pub(crate) Option<Number>);
+
+macro_rules! first_number {
+    ($($m:ident: $ty:ty),*) => {
+        $(
+            #[inline]
+            fn $m(&mut self, value: $ty) {
+                self.0.get_or_insert(value.into());
+            }
+        )*
+    };
+}
+
+impl Receiver for FirstNumber {
+    first_number!(
+        i8: i8, u8: u8, i16: i16, u16: u16, i32: i32, u32: u32,
+        i64: i64, u64: u64, i128: i128, u128: u128, f32: f32, f64: f64
+    );
+
+    #[inline]
+    fn accepting() -> impl crate::rr::typeset::TypeSet + 'static {
+        crate::rr::typeset::NUMERIC
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn across_widths() {
+        assert!(Number::from(-1i8) < Number::from(0u128));
+        assert!(Number::from(u64::MAX) > Number::from(i64::MAX));
+        assert_eq!(Number::from(3u8), Number::from(3i64));
+        assert_eq!(Number::from(3u8), Number::from(3.0f32));
+    }
+
+    #[test]
+    fn with_floats() {
+        assert!(Number::from(2u8) < Number::from(2.5));
+        assert!(Number::from(3u8) > Number::from(2.5));
+        assert!(Number::from(-3i8) < Number::from(-2.5));
+        assert!(Number::from(-2i8) > Number::from(-2.5));
+        assert!(Number::from(0u8) > Number::from(-0.5));
+        assert!(Number::from(u128::MAX) < Number::from(f64::INFINITY));
+        assert!(Number::from(i128::MIN) > Number::from(f64::NEG_INFINITY));
+        // Would compare equal if converted to f64
+        assert!(Number::from(u64::MAX) < Number::from(18_446_744_073_709_551_616.0));
+    }
+
+    #[test]
+    fn filters() {
+        use crate::query::{DataFilter, Filter};
+
+        assert!(DataFilter::lt(10u8).matches(&9.5f64));
+        assert!(!DataFilter::lt(10u8).matches(&10i64));
+        assert!(DataFilter::le(10u8).matches(&10i64));
+        assert!(DataFilter::gt(-1i8).matches(&0u64));
+        assert!(DataFilter::ge(0.5).matches(&1u8));
+        assert!(DataFilter::eq_num(3u8).matches(&3.0f32));
+        assert!(!DataFilter::eq_num(3u8).matches(&"3"));
+    }
+
+    #[test]
+    fn truncated_copies() {
+        use crate::query::{DataFilter, Filter};
+
+        // 300 as u8 is 44
+        assert!(!DataFilter::lt(50u8).matches(&300usize));
+        assert!(!DataFilter::lt(50u8).matches_typed(&300usize));
+        assert!(!DataFilter::eq_num(44u8).matches(&300usize));
+        assert!(DataFilter::eq_num(300u16).matches(&300usize));
+        assert!(DataFilter::between(256u16, 1000u16).matches_typed(&300usize));
+
+        // -300 as i8 is -44
+        assert!(!DataFilter::gt(-50i8).matches(&-300isize));
+        assert!(!DataFilter::eq_num(-44i8).matches_typed(&-300isize));
+        assert!(DataFilter::lt(-299i16).matches(&-300isize));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn link_filters() {
+        use crate::data::{BoxedData, DataExt};
+        use crate::query::{DataFilter, LinkFilter, Query};
+        use std::collections::HashMap;
+
+        let map = HashMap::from([(1u8, 100u32), (20u8, 200u32)]);
+
+        let by_key = Query::new(LinkFilter::key(DataFilter::gt(10u8)));
+        let targets: Vec<BoxedData> = map.query(&by_key).unwrap();
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].as_u32(), Some(200));
+
+        let by_target = Query::new(LinkFilter::target(DataFilter::between(50u8, 150u8)));
+        let targets: Vec<BoxedData> = map.query(&by_target).unwrap();
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].as_u32(), Some(100));
+    }
+
+    #[test]
+    fn optimize() {
+        use crate::query::{DataFilter, Optimizable};
+
+        assert_eq!(DataFilter::between(2u8, 1u8).as_bool(), Some(false));
+        assert_eq!(DataFilter::lt(f64::NAN).as_bool(), Some(false));
+
+        let mut single = DataFilter::between(1u8, 1.0);
+        single.optimize();
+        assert!(matches!(single, DataFilter::EqNum(_)));
+    }
+
+    #[test]
+    fn nan() {
+        let nan = Number::from(f64::NAN);
+        assert!(nan.partial_cmp(&Number::from(1u8)).is_none());
+        assert!(nan != nan);
+    }
+}
@@ -0,0 +1,154 @@
+//! Following paths of exact keys through nested data
+
+use std::rc::Rc;
+
+use super::{DataFilter, LinkFilter, Query};
+use crate::data::{BoxedData, Data};
+use crate::links::{LinkError, Links, Result, BREAK, CONTINUE};
+use crate::rr::Request;
+use crate::value::{AllValues, Value};
+
+/// Query for the links with key `key`
+pub(crate) fn step(key: &str) -> Query {
+    Query::new(LinkFilter::key(DataFilter::exact(key)))
+}
+
+/// One query per step of a key path
+pub(crate) fn steps<S: AsRef<str>>(path: &[S]) -> Vec<Query> {
+    path.iter().map(|key| step(key.as_ref())).collect()
+}
+
+/// Matches data where a value at `path` matches `filter`
+pub(crate) fn linked_at<S: AsRef<str>>(path: &[S], filter: DataFilter) -> DataFilter {
+    path.iter().rev().fold(filter, |filter, key| {
+        DataFilter::linked(
+            LinkFilter::key(DataFilter::exact(key.as_ref())) & LinkFilter::target(filter),
+        )
+    })
+}
+
+/// Pushes the data at the remaining `steps` below `target` to `inner`
+pub(crate) fn follow<A: Links + ?Sized>(
+    steps: &[Query],
+    inner: &mut A,
+    target: BoxedData,
+    key: Option<BoxedData>,
+) -> Result {
+    let Some((step, rest)) = steps.split_first() else {
+        return inner.push(target, key);
+    };
+    let mut next = Step::new(rest, inner);
+    target.query_links(&mut next, step)?;
+    if next.done {
+        BREAK
+    } else {
+        CONTINUE
+    }
+}
+
+/// Follows the remaining steps for each link found by a step
+pub(crate) struct Step<'s, 'a, A: ?Sized> {
+    rest: &'s [Query],
+    inner: &'a mut A,
+    done: bool,
+}
+
+impl<'s, 'a, A: ?Sized> Step<'s, 'a, A> {
+    #[inline]
+    pub(crate) fn new(rest: &'s [Query], inner: &'a mut A) -> Self {
+        Self {
+            rest,
+            inner,
+            done: false,
+        }
+    }
+}
+
+impl<A: Links + ?Sized> Links for Step<'_, '_, A> {
+    #[inline]
+    fn push(&mut self, target: BoxedData, key: Option<BoxedData>) -> Result {
+        let flow = follow(self.rest, self.inner, target, key)?;
+        self.done = flow.is_break();
+        Ok(flow)
+    }
+}
+
+/// The first value found at `steps` below `data`
+pub(crate) fn value_at(data: &dyn Data, steps: &[Query]) -> Result<Option<Value>, LinkError> {
+    let Some((step, rest)) = steps.split_first() else {
+        return Ok(first_value(data));
+    };
+    let mut found = FirstValue(None);
+    data.query_links(&mut Step::new(rest, &mut found), step)?;
+    Ok(found.0)
+}
+
+/// The first data found at `steps` below `data`, `None` for an empty path
+pub(crate) fn node_at(data: &dyn Data, steps: &[Query]) -> Result<Option<Rc<dyn Data>>, LinkError> {
+    let Some((step, rest)) = steps.split_first() else {
+        return Ok(None);
+    };
+    let mut found = FirstNode(None);
+    data.query_links(&mut Step::new(rest, &mut found), step)?;
+    Ok(found.0)
+}
+
+/// The first value `data` provides, which for numbers is the widest
+pub(crate) fn first_value(data: &dyn Data) -> Option<Value> {
+    let mut values = AllValues::default();
+    data.provide_value(&mut Request::new_erased(&mut values));
+    values.into_iter().next()
+}
+
+/// Stops at the first target with a value
+struct FirstValue(Option<Value>);
+
+impl Links for FirstValue {
+    #[inline]
+    fn push(&mut self, target: BoxedData, _key: Option<BoxedData>) -> Result {
+        self.0 = first_value(target.as_ref());
+        if self.0.is_some() {
+            BREAK
+        } else {
+            CONTINUE
+        }
+    }
+}
+
+/// Keeps the first target found
+struct FirstNode(Option<Rc<dyn Data>>);
+
+impl Links for FirstNode {
+    #[inline]
+    fn push(&mut self, target: BoxedData, _key: Option<BoxedData>) -> Result {
+        self.0 = Some(target.into());
+        BREAK
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::Filter;
+    use std::collections::HashMap;
+
+    #[test]
+    fn values() {
+        let record = HashMap::from([("user", HashMap::from([("age", 36u8)]))]);
+
+        let age = steps(&["user", "age"]);
+        assert_eq!(value_at(&record, &age).unwrap(), Some(Value::U8(36)));
+        assert!(node_at(&record, &age).unwrap().is_some());
+        assert_eq!(value_at(&record, &steps(&["user", "name"])).unwrap(), None);
+        assert_eq!(value_at(&36u8, &[]).unwrap(), Some(Value::U8(36)));
+    }
+
+    #[test]
+    fn filters() {
+        let record = HashMap::from([("user", HashMap::from([("age", 36u8)]))]);
+
+        assert!(linked_at(&["user", "age"], DataFilter::gt(18u8)).matches(&record));
+        assert!(!linked_at(&["user", "age"], DataFilter::lt(18u8)).matches(&record));
+        assert!(!linked_at(&["age"], DataFilter::Any).matches(&record));
+    }
+}
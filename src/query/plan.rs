@@ -0,0 +1,215 @@
+use std::num::NonZeroUsize;
+
+use crate::links::{LinkError, Links, LinksExt, Result, BREAK, CONTINUE};
+use crate::{BoxedData, Data};
+
+use super::selection::Projecting;
+use super::{LinkFilter, Query};
+
+/// A [`Query`] split into the part a datum evaluates itself and a residual filter
+///
+/// Filters the datum reports as supported by [`Data::supports_filter`] are pushed down to
+/// [`Data::query_links`], the residual is applied to the links it returns. A top level
+/// [`LinkFilter::And`] is split into its parts, any other filter is pushed down as a whole
+/// or not at all.
+///
+/// ```rust
+/// use datalink::query::{DataFilter, LinkFilter, Plan, Query};
+///
+/// let query = Query::new(LinkFilter::Any & LinkFilter::target(DataFilter::gt(1u8)));
+/// let plan = Plan::new(query, &vec![1u8, 2, 3]);
+///
+/// assert!(matches!(plan.pushed().filter(), LinkFilter::Any));
+/// assert!(matches!(plan.residual(), LinkFilter::Target(_)));
+/// ```
+#[derive(Debug)]
+pub struct Plan {
+    pushed: Query,
+    residual: LinkFilter,
+    limit: Option<NonZeroUsize>,
+}
+
+impl Plan {
+    #[inline]
+    #[must_use]
+    pub fn new(query: Query, data: &(impl Data + ?Sized)) -> Self {
+        let Query {
+            filter,
+            limit,
+            selection,
+        } = query;
+
+        let (pushed, residual) = if data.supports_filter(&filter) {
+            (filter, LinkFilter::Any)
+        } else if let LinkFilter::And(mut and) = filter {
+            let (pushed, residual): (Vec<_>, Vec<_>) =
+                and.drain(..).partition(|f| data.supports_filter(f));
+            (conjunction(pushed), conjunction(residual))
+        } else {
+            (LinkFilter::Any, filter)
+        };
+
+        let pushed = Query {
+            filter: pushed,
+            // The limit only applies to the links after the residual filter
+            limit: limit.filter(|_| matches!(residual, LinkFilter::Any)),
+            selection,
+        };
+        Self {
+            pushed,
+            residual,
+            limit,
+        }
+    }
+
+    /// The query passed to [`Data::query_links`]
+    #[inline]
+    #[must_use]
+    pub const fn pushed(&self) -> &Query {
+        &self.pushed
+    }
+
+    /// The filter applied to the links returned for [`pushed`](Plan::pushed)
+    #[inline]
+    #[must_use]
+    pub const fn residual(&self) -> &LinkFilter {
+        &self.residual
+    }
+
+    /// Runs the plan against `data`, which should be the datum it was planned for
+    ///
+    /// # Errors
+    /// Any error returned by [`Data::query_links`].
+    #[inline]
+    pub fn execute(
+        &self,
+        data: &(impl Data + ?Sized),
+        links: &mut dyn Links,
+    ) -> core::result::Result<(), LinkError> {
+        if matches!(self.residual, LinkFilter::Any) {
+            return self.pushed.run(data, links);
+        }
+        // The residual filter sees whole targets, only the kept links are projected
+        let mut projecting;
+        let links: &mut dyn Links = match &self.pushed.selection {
+            Some(selection) => {
+                projecting = Projecting {
+                    inner: links,
+                    selection,
+                };
+                &mut projecting
+            }
+            None => links,
+        };
+        let mut limited = Limited::new(links, self.limit.map_or(usize::MAX, NonZeroUsize::get));
+        data.query_links(&mut limited.filter(&self.residual), &self.pushed)
+    }
+}
+
+fn conjunction(mut filters: Vec<LinkFilter>) -> LinkFilter {
+    match filters.len() {
+        0 => LinkFilter::Any,
+        1 => filters.pop().unwrap_or_default(),
+        _ => LinkFilter::And(filters.into()),
+    }
+}
+
+/// Stops after `remaining` links
+pub(super) struct Limited<'l> {
+    inner: &'l mut dyn Links,
+    remaining: usize,
+}
+
+impl<'l> Limited<'l> {
+    #[inline]
+    pub(super) fn new(inner: &'l mut dyn Links, limit: usize) -> Self {
+        Self {
+            inner,
+            remaining: limit,
+        }
+    }
+}
+
+impl Links for Limited<'_> {
+    #[inline]
+    fn push(&mut self, target: BoxedData, key: Option<BoxedData>) -> Result {
+        if self.remaining == 0 {
+            return BREAK;
+        }
+        self.remaining -= 1;
+        let flow = self.inner.push(target, key)?;
+        if self.remaining == 0 {
+            BREAK
+        } else if flow.is_break() {
+            Ok(flow)
+        } else {
+            CONTINUE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::{DataFilter, Filter};
+
+    /// Only evaluates key filters, like a remote index would
+    #[derive(Debug)]
+    struct Remote;
+
+    impl Data for Remote {
+        fn provide_links(&self, links: &mut dyn Links) -> core::result::Result<(), LinkError> {
+            links.push_link(("a", 1u8))?;
+            links.push_link(("b", 2u8))?;
+            links.push_link(("c", 3u8))?;
+            Ok(())
+        }
+        fn query_links(
+            &self,
+            links: &mut dyn Links,
+            query: &Query,
+        ) -> core::result::Result<(), LinkError> {
+            if !self.supports_filter(query.filter()) {
+                return Err(LinkError::UnsupportedQuery);
+            }
+            self.provide_links(&mut links.filter(query.filter()))
+        }
+        fn supports_filter(&self, filter: &LinkFilter) -> bool {
+            matches!(filter, LinkFilter::Any | LinkFilter::Key(_))
+        }
+    }
+
+    fn query() -> Query {
+        Query::new(
+            LinkFilter::key(!DataFilter::text("b")) & LinkFilter::target(DataFilter::gt(1u8)),
+        )
+    }
+
+    #[test]
+    fn split() {
+        let plan = Plan::new(query(), &Remote);
+        assert!(matches!(plan.pushed().filter(), LinkFilter::Key(_)));
+        assert!(matches!(plan.residual(), LinkFilter::Target(_)));
+
+        let mut links: Vec<(BoxedData, BoxedData)> = Vec::new();
+        assert!(matches!(
+            Remote.query_links(&mut links, &query()),
+            Err(LinkError::UnsupportedQuery)
+        ));
+        plan.execute(&Remote, &mut links).unwrap();
+        assert_eq!(links.len(), 1);
+        assert!(DataFilter::eq_num(3u8).matches(&links[0].1));
+    }
+
+    #[test]
+    fn limit() {
+        let query = Query::new(LinkFilter::target(DataFilter::gt(1u8))).with_limit(1);
+        let plan = Plan::new(query, &Remote);
+        assert_eq!(plan.pushed().limit(), usize::MAX);
+
+        let mut links: Vec<(BoxedData, BoxedData)> = Vec::new();
+        plan.execute(&Remote, &mut links).unwrap();
+        assert_eq!(links.len(), 1);
+        assert!(DataFilter::eq_num(2u8).matches(&links[0].1));
+    }
+}
@@ -0,0 +1,97 @@
+use crate::rr::provided::Provided;
+use crate::rr::query::Borrowed;
+use crate::rr::{Receiver, Request};
+use crate::Data;
+
+/// How value filters ask a datum for its values
+pub(super) trait Provide<D: ?Sized> {
+    fn provide<R: Receiver>(d: &D, receiver: &mut R);
+}
+
+/// Through [`Data::provide_value`], works for unsized data
+#[derive(Debug)]
+pub(super) struct Erased;
+
+impl<D: Data + ?Sized> Provide<D> for Erased {
+    #[inline]
+    fn provide<R: Receiver>(d: &D, receiver: &mut R) {
+        d.provide_value(&mut Request::new_erased(receiver));
+    }
+}
+
+/// Through [`Data::provide_requested`], falling back to [`Erased`] if that didn't provide
+#[derive(Debug)]
+pub(super) struct Typed;
+
+impl<D: Data> Provide<D> for Typed {
+    #[inline]
+    fn provide<R: Receiver>(d: &D, receiver: &mut R) {
+        let mut request = Request::<Borrowed<R>>::new(receiver);
+        if !d.provide_requested(&mut request).was_provided() {
+            d.provide_value(&mut request.as_erased());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use crate::query::{DataFilter, Filter};
+    use crate::rr::provided::Provided;
+    use crate::rr::{Query, Request};
+    use crate::Data;
+
+    #[derive(Debug, Default)]
+    struct Counted {
+        erased: Cell<usize>,
+        typed: bool,
+    }
+
+    impl Data for Counted {
+        fn provide_value(&self, request: &mut Request) {
+            self.erased.set(self.erased.get() + 1);
+            request.provide_str("text");
+        }
+        fn provide_requested<Q: Query>(&self, request: &mut Request<Q>) -> impl Provided {
+            if self.typed {
+                request.provide_str("text");
+            }
+            self.typed
+        }
+    }
+
+    #[test]
+    fn typed() {
+        let d = Counted {
+            typed: true,
+            ..Counted::default()
+        };
+        assert!(DataFilter::text("text").matches_typed(&d));
+        assert_eq!(d.erased.get(), 0);
+        assert!(DataFilter::text("text").matches(&d));
+        assert_eq!(d.erased.get(), 1);
+    }
+
+    #[test]
+    fn fallback() {
+        let d = Counted::default();
+        assert!(DataFilter::text("text").matches_typed(&d));
+        assert!(!DataFilter::text("other").matches_typed(&d));
+        assert_eq!(d.erased.get(), 2);
+    }
+
+    #[test]
+    fn same_results() {
+        let filters = [
+            DataFilter::text("a") | DataFilter::gt(1u8),
+            !DataFilter::is_number(),
+            DataFilter::unique(),
+        ];
+        for f in &filters {
+            assert_eq!(f.matches_typed(&"a"), f.matches(&"a"));
+            assert_eq!(f.matches_typed(&2u8), f.matches(&2u8));
+            assert_eq!(f.matches_typed(&String::new()), f.matches(&String::new()));
+        }
+    }
+}
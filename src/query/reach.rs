@@ -0,0 +1,131 @@
+use std::collections::HashSet;
+
+use filters::Filter;
+
+use crate::id::ID;
+use crate::links::{Links, MaybeKeyed, Result, BREAK, CONTINUE};
+use crate::{BoxedData, Data};
+
+use super::{DataFilter, LinkFilter};
+
+/// Whether a node matching `target` can be reached from `d` in at most `max_depth` steps
+///
+/// Only links matching `edge` are followed and the search runs breadth first, so the
+/// nearest match ends it. Nodes with an ID are visited once, which breaks cycles between
+/// them. Cycles of nodes without an ID are only bounded by `max_depth`.
+pub(super) fn reachable(
+    d: &(impl Data + ?Sized),
+    max_depth: usize,
+    edge: &LinkFilter,
+    target: &DataFilter,
+) -> bool {
+    if max_depth == 0 {
+        return false;
+    }
+    let mut search = Search {
+        edge,
+        target,
+        visited: d.get_id().into_iter().collect(),
+        frontier: Vec::new(),
+        expand: max_depth > 1,
+        found: false,
+    };
+    let _ = d.provide_links(&mut search);
+
+    let mut depth = 1;
+    while !search.found && !search.frontier.is_empty() {
+        depth += 1;
+        search.expand = depth < max_depth;
+        for node in std::mem::take(&mut search.frontier) {
+            let _ = node.provide_links(&mut search);
+            if search.found {
+                break;
+            }
+        }
+    }
+    search.found
+}
+
+struct Search<'a> {
+    edge: &'a LinkFilter,
+    target: &'a DataFilter,
+    visited: HashSet<ID>,
+    /// Nodes to expand at the next depth
+    frontier: Vec<BoxedData>,
+    expand: bool,
+    found: bool,
+}
+
+impl Links for Search<'_> {
+    #[inline]
+    fn push(&mut self, target: BoxedData, key: Option<BoxedData>) -> Result {
+        let link = MaybeKeyed::new(key, target);
+        if !self.edge.matches(&link) {
+            return CONTINUE;
+        }
+        let (MaybeKeyed::Keyed(_, target) | MaybeKeyed::Unkeyed(target)) = link;
+        if target.get_id().is_some_and(|id| !self.visited.insert(id)) {
+            return CONTINUE;
+        }
+        if Filter::<BoxedData>::matches(self.target, &target) {
+            self.found = true;
+            return BREAK;
+        }
+        if self.expand {
+            self.frontier.push(target);
+        }
+        CONTINUE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::links::{LinkError, Links, LinksExt};
+    use crate::query::{DataFilter, Filter, LinkFilter};
+    use crate::Data;
+
+    #[test]
+    fn depth() {
+        let nested = vec![vec![vec![5u8]]];
+        let five = || DataFilter::reachable(3, LinkFilter::Any, DataFilter::eq_num(5u8));
+        assert!(five().matches(&nested));
+        assert!(
+            !DataFilter::reachable(2, LinkFilter::Any, DataFilter::eq_num(5u8)).matches(&nested)
+        );
+        assert!(!DataFilter::reachable(0, LinkFilter::Any, DataFilter::Any).matches(&nested));
+        assert!(!five().matches(&5u8));
+    }
+
+    #[test]
+    fn edges() {
+        let nested = vec![vec![5u8], vec![6u8]];
+        let no_five = LinkFilter::target(!DataFilter::linked(LinkFilter::target(
+            DataFilter::eq_num(5u8),
+        )));
+        assert!(!DataFilter::reachable(2, no_five, DataFilter::eq_num(5u8)).matches(&nested));
+    }
+
+    #[test]
+    fn cycles() {
+        #[derive(Debug, Clone, Copy)]
+        struct Node(u128);
+        impl Data for Node {
+            fn provide_links(&self, links: &mut dyn Links) -> Result<(), LinkError> {
+                // 1 -> 2 -> 1
+                links.push_link(Node(self.0 % 2 + 1))?;
+                Ok(())
+            }
+            fn get_id(&self) -> Option<crate::id::ID> {
+                crate::id::ID::try_new(self.0).ok()
+            }
+        }
+        let never = DataFilter::reachable(usize::MAX, LinkFilter::Any, DataFilter::None);
+        assert!(!never.matches(&Node(1)));
+        let two = DataFilter::reachable(
+            usize::MAX,
+            LinkFilter::Any,
+            DataFilter::id(Node(2).get_id().unwrap()),
+        );
+        assert!(two.matches(&Node(1)));
+    }
+}
@@ -0,0 +1,457 @@
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::data::{BoxedData, Data};
+use crate::links::{LinkError, Links, Result, BREAK, CONTINUE};
+use crate::value::{Provided, ValueQuery, ValueRequest};
+
+use super::{path, Query};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SelectionError {
+    #[error("Unexpected {0:?} at {1}")]
+    Unexpected(char, usize),
+    #[error("Unexpected end of selection")]
+    UnexpectedEnd,
+}
+
+/// Which keyed links to keep at each level of a projected [`Data`]
+///
+/// A field without its own selection keeps the whole target of its links.
+/// Selections can be built field by field or parsed from a GraphQL-like syntax,
+/// where fields are separated by commas or whitespace and nested in braces.
+///
+/// ```rust
+/// use datalink::query::Selection;
+///
+/// let parsed: Selection = "name, address { city }".parse().unwrap();
+/// let built = Selection::new()
+///     .field("name")
+///     .nested("address", Selection::new().field("city"));
+/// assert_eq!(parsed, built);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Selection {
+    fields: Vec<Field>,
+}
+
+/// A selected key, with the query looking it up built once
+#[derive(Debug)]
+struct Field {
+    key: String,
+    query: Query,
+    selection: Option<Arc<Selection>>,
+}
+
+impl Field {
+    fn new(key: String, selection: Option<Arc<Selection>>) -> Self {
+        Self {
+            query: path::step(&key),
+            key,
+            selection,
+        }
+    }
+}
+
+impl Clone for Field {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.key.clone(), self.selection.clone())
+    }
+}
+
+impl PartialEq for Field {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key && self.selection == other.selection
+    }
+}
+
+impl Eq for Field {}
+
+impl Selection {
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { fields: Vec::new() }
+    }
+
+    /// Keeps the links with key `key` as they are
+    #[inline]
+    #[must_use]
+    pub fn field(mut self, key: impl Into<String>) -> Self {
+        self.fields.push(Field::new(key.into(), None));
+        self
+    }
+
+    /// Keeps the links with key `key`, projecting their targets to `selection`
+    #[inline]
+    #[must_use]
+    pub fn nested(mut self, key: impl Into<String>, selection: Selection) -> Self {
+        self.fields
+            .push(Field::new(key.into(), Some(Arc::new(selection))));
+        self
+    }
+
+    /// Keys of the selected fields
+    #[inline]
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.fields.iter().map(|f| f.key.as_str())
+    }
+
+    /// Selection of the targets of a field, `None` if it isn't selected or keeps the whole target
+    #[inline]
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Selection> {
+        let field = self.fields.iter().find(|f| f.key == key)?;
+        field.selection.as_deref()
+    }
+
+    /// Parses a selection like `name, address { city }`
+    ///
+    /// # Errors
+    /// If braces don't match or a field name is missing.
+    #[inline]
+    pub fn parse(s: &str) -> core::result::Result<Self, SelectionError> {
+        let mut parser = Parser { s, pos: 0 };
+        let selection = parser.fields()?;
+        match parser.peek() {
+            None => Ok(selection),
+            Some(c) => Err(SelectionError::Unexpected(c, parser.pos)),
+        }
+    }
+}
+
+impl FromStr for Selection {
+    type Err = SelectionError;
+
+    #[inline]
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+struct Parser<'s> {
+    s: &'s str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    /// Next character after whitespace and commas
+    fn peek(&mut self) -> Option<char> {
+        let rest = &self.s[self.pos..];
+        let trimmed = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        self.pos += rest.len() - trimmed.len();
+        trimmed.chars().next()
+    }
+
+    fn fields(&mut self) -> core::result::Result<Selection, SelectionError> {
+        let mut selection = Selection::new();
+        while let Some(c) = self.peek() {
+            if c == '}' {
+                break;
+            }
+            let key = self.name()?;
+            if self.peek() == Some('{') {
+                self.pos += 1;
+                let nested = self.fields()?;
+                match self.peek() {
+                    Some('}') => self.pos += 1,
+                    Some(c) => return Err(SelectionError::Unexpected(c, self.pos)),
+                    None => return Err(SelectionError::UnexpectedEnd),
+                }
+                selection = selection.nested(key, nested);
+            } else {
+                selection = selection.field(key);
+            }
+        }
+        Ok(selection)
+    }
+
+    fn name(&mut self) -> core::result::Result<String, SelectionError> {
+        let rest = &self.s[self.pos..];
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let end = quoted.find('"').ok_or(SelectionError::UnexpectedEnd)?;
+            self.pos += end + 2;
+            return Ok(quoted[..end].to_owned());
+        }
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(rest
+                .chars()
+                .next()
+                .map_or(SelectionError::UnexpectedEnd, |c| {
+                    SelectionError::Unexpected(c, self.pos)
+                }));
+        }
+        self.pos += len;
+        Ok(rest[..len].to_owned())
+    }
+}
+
+/// Lazy view of data which only exposes the links of a [`Selection`]
+///
+/// Values are provided as by the wrapped data. Links are looked up by key from the wrapped
+/// data each time they're requested, so only the selected subtrees are ever read.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use datalink::data::DataExt;
+/// use datalink::query::{Projected, Selection};
+///
+/// let user = HashMap::from([
+///     ("name", HashMap::from([("first", "ada")])),
+///     ("address", HashMap::from([("city", "london"), ("street", "1 main st")])),
+/// ]);
+/// let view = Projected::new(&user, Selection::parse("address { city }").unwrap());
+///
+/// let items = view.as_items().unwrap();
+/// assert_eq!(items.len(), 1);
+/// assert_eq!(items[0].1.as_items().unwrap().len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct Projected<D> {
+    data: D,
+    selection: Arc<Selection>,
+}
+
+impl<D: Data> Projected<D> {
+    #[inline]
+    #[must_use]
+    pub fn new(data: D, selection: impl Into<Arc<Selection>>) -> Self {
+        Self {
+            data,
+            selection: selection.into(),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn selection(&self) -> &Selection {
+        &self.selection
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> D {
+        self.data
+    }
+}
+
+impl<D: Data> Data for Projected<D> {
+    #[inline]
+    fn provide_value(&self, request: &mut ValueRequest) {
+        self.data.provide_value(request);
+    }
+
+    #[inline]
+    fn provide_requested<Q: ValueQuery>(&self, request: &mut ValueRequest<Q>) -> impl Provided {
+        self.data.provide_requested(request)
+    }
+
+    #[inline]
+    fn provide_links(&self, links: &mut dyn Links) -> core::result::Result<(), LinkError> {
+        for field in &self.selection.fields {
+            let mut field_links = FieldLinks {
+                inner: links,
+                selection: field.selection.as_ref(),
+                done: false,
+            };
+            self.data.query_links(&mut field_links, &field.query)?;
+            if field_links.done {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "unique"), doc(hidden))]
+    #[inline]
+    fn get_id(&self) -> Option<crate::id::ID> {
+        self.data.get_id()
+    }
+}
+
+/// Projects the targets of one field
+struct FieldLinks<'l, 's> {
+    inner: &'l mut dyn Links,
+    selection: Option<&'s Arc<Selection>>,
+    done: bool,
+}
+
+impl Links for FieldLinks<'_, '_> {
+    #[inline]
+    fn push(&mut self, target: BoxedData, key: Option<BoxedData>) -> Result {
+        let target = match self.selection {
+            Some(selection) => Box::new(Projected::new(target, Arc::clone(selection))),
+            None => target,
+        };
+        let flow = self.inner.push(target, key)?;
+        self.done = flow.is_break();
+        Ok(flow)
+    }
+}
+
+/// Projects every target pushed to the wrapped links
+pub(crate) struct Projecting<'l, 's> {
+    pub(crate) inner: &'l mut dyn Links,
+    pub(crate) selection: &'s Arc<Selection>,
+}
+
+impl Links for Projecting<'_, '_> {
+    #[inline]
+    fn push(&mut self, target: BoxedData, key: Option<BoxedData>) -> Result {
+        let target = Box::new(Projected::new(target, Arc::clone(self.selection)));
+        if self.inner.push(target, key)?.is_break() {
+            BREAK
+        } else {
+            CONTINUE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::DataExt;
+    use crate::links::LinksExt;
+    use crate::query::KeyHint;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    #[test]
+    fn parse() {
+        let selection = Selection::parse(r#"user { name "home address" { city } } id"#).unwrap();
+        assert_eq!(selection.keys().collect::<Vec<_>>(), ["user", "id"]);
+        let user = selection.get("user").unwrap();
+        assert_eq!(user.keys().collect::<Vec<_>>(), ["name", "home address"]);
+        assert!(user.get("home address").is_some());
+        assert!(user.get("name").is_none());
+
+        assert!(matches!(
+            Selection::parse("user { name"),
+            Err(SelectionError::UnexpectedEnd)
+        ));
+        assert!(matches!(
+            Selection::parse("user }"),
+            Err(SelectionError::Unexpected('}', 5))
+        ));
+        assert!(matches!(
+            Selection::parse("{ name }"),
+            Err(SelectionError::Unexpected('{', 0))
+        ));
+    }
+
+    #[test]
+    fn nested() {
+        let data = HashMap::from([(
+            "user",
+            HashMap::from([
+                (
+                    "name",
+                    HashMap::from([("first", "ada"), ("last", "lovelace")]),
+                ),
+                (
+                    "address",
+                    HashMap::from([("city", "london"), ("zip", "N1")]),
+                ),
+            ]),
+        )]);
+        let view = Projected::new(
+            &data,
+            Selection::parse("user { address { city } }").unwrap(),
+        );
+
+        let user = view.as_items().unwrap();
+        assert_eq!(user.len(), 1);
+        let fields = user[0].1.as_items().unwrap();
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].0.as_str().as_deref(), Some("address"));
+        let address = fields[0].1.as_items().unwrap();
+        assert_eq!(address.len(), 1);
+        assert_eq!(address[0].1.as_str().as_deref(), Some("london"));
+    }
+
+    /// Only answers lookups by key and records them
+    #[derive(Debug, Default)]
+    struct Remote {
+        fields: HashMap<&'static str, u32>,
+        fetched: RefCell<Vec<String>>,
+    }
+
+    impl Data for Remote {
+        fn provide_links(&self, _links: &mut dyn Links) -> core::result::Result<(), LinkError> {
+            Err(LinkError::UnsupportedQuery)
+        }
+
+        fn query_links(
+            &self,
+            links: &mut dyn Links,
+            query: &Query,
+        ) -> core::result::Result<(), LinkError> {
+            let Some(KeyHint::Exact(keys)) = query.filter().key_hint() else {
+                return Err(LinkError::UnsupportedQuery);
+            };
+            for key in keys {
+                self.fetched.borrow_mut().push(key.to_owned());
+                if let Some((&k, &v)) = self.fields.get_key_value(key) {
+                    links.push_link((k, v))?;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn fetches_selected() {
+        let remote = Remote {
+            fields: HashMap::from([("id", 1), ("age", 36), ("score", 9)]),
+            ..Remote::default()
+        };
+        let view = Projected::new(&remote, Selection::new().field("id").field("nickname"));
+
+        let items = view.as_items().unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].1.as_u32(), Some(1));
+        assert_eq!(*remote.fetched.borrow(), ["id", "nickname"]);
+    }
+
+    #[test]
+    fn query() {
+        use crate::query::{DataFilter, LinkFilter, Plan};
+
+        let users = vec![
+            HashMap::from([("name", "ada"), ("role", "admin")]),
+            HashMap::from([("name", "bob"), ("role", "user")]),
+        ];
+        let admins = Query::new(LinkFilter::target(path::linked_at(
+            &["role"],
+            DataFilter::exact("admin"),
+        )))
+        .with_selection(Selection::new().field("name"));
+        assert_eq!(admins.selection().map(|s| s.keys().count()), Some(1));
+
+        let found: Vec<BoxedData> = users.query(&admins).unwrap();
+        assert_eq!(found.len(), 1);
+        let fields = found[0].as_items().unwrap();
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].1.as_str().as_deref(), Some("ada"));
+
+        // The residual filter still sees the unselected role
+        let mut planned = Vec::<BoxedData>::new();
+        Plan::new(admins, &users)
+            .execute(&users, &mut planned)
+            .unwrap();
+        assert_eq!(planned.len(), 1);
+        assert_eq!(planned[0].as_items().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Selection>();
+        assert_send_sync::<Query>();
+    }
+}
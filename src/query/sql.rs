@@ -0,0 +1,970 @@
+//! A small SQL dialect for selecting links
+//!
+//! ```text
+//! SELECT * | path [AS name], ...
+//! [FROM path]
+//! [WHERE condition]
+//! [ORDER BY path [ASC | DESC], ...]
+//! [LIMIT n]
+//! ```
+//!
+//! A path is a dot separated list of keys, which can be quoted with `"` if they aren't plain
+//! identifiers. `FROM` selects the data whose links are the rows, by default the queried data itself.
+//! Conditions compare the value at a path of each row with `=`, `!=`, `<>`, `<`, `<=`, `>`, `>=`,
+//! `LIKE`, `BETWEEN`, `IN` and `IS [NOT] NULL`, and combine with `AND`, `OR`, `NOT` and parentheses.
+//! Like `NULL` in SQL, a missing value or one of another kind matches neither a comparison nor
+//! its negation. Missing values match `IS NULL`.
+//! They compile to a [`LinkFilter`] over the rows, which is planned with [`Plan`](super::Plan)
+//! so it's pushed down where the data supports it.
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//! use datalink::data::DataExt;
+//! use datalink::query::sql::Select;
+//!
+//! let data = HashMap::from([(
+//!     "users",
+//!     vec![
+//!         HashMap::from([("name", "ada"), ("role", "admin")]),
+//!         HashMap::from([("name", "bob"), ("role", "user")]),
+//!     ],
+//! )]);
+//!
+//! let select: Select = "SELECT name FROM users WHERE role = 'admin'".parse().unwrap();
+//! let rows = select.execute(&data).unwrap();
+//! assert_eq!(rows.len(), 1);
+//!
+//! let (column, name) = rows[0].as_items().unwrap().pop().unwrap();
+//! assert_eq!(column.as_str().as_deref(), Some("name"));
+//! assert_eq!(name.as_str().as_deref(), Some("ada"));
+//! ```
+
+use std::cmp::Ordering;
+use std::ops::Bound;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use super::path::{linked_at, node_at, steps, value_at, Step};
+use super::{DataFilter, LinkFilter, Number, Plan, Query};
+use crate::data::{BoxedData, Data};
+use crate::links::{LinkError, Links, Result, BREAK, CONTINUE};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SqlError {
+    #[error("Unexpected {found} at {position}, expected {expected}")]
+    Unexpected {
+        found: String,
+        position: usize,
+        expected: &'static str,
+    },
+    #[error("Unexpected end of input, expected {0}")]
+    UnexpectedEnd(&'static str),
+    #[error("Invalid number {0}")]
+    InvalidNumber(String),
+    #[error("Unterminated quote at {0}")]
+    UnterminatedQuote(usize),
+    #[error("Unsupported pattern {0}, only a trailing % is supported")]
+    UnsupportedPattern(String),
+}
+
+/// A parsed `SELECT` statement
+#[derive(Debug, Clone)]
+pub struct Select {
+    columns: Option<Vec<Column>>,
+    from: Vec<String>,
+    condition: Option<Condition>,
+    order: Vec<(Vec<String>, Direction)>,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct Column {
+    path: Vec<String>,
+    name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone)]
+enum Condition {
+    And(Vec<Condition>),
+    Or(Vec<Condition>),
+    Not(Box<Condition>),
+    Compare(Vec<String>, Op, Literal),
+    Like(Vec<String>, String),
+    Between(Vec<String>, Number, Number),
+    In(Vec<String>, Vec<Literal>),
+    NotNull(Vec<String>),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone)]
+enum Literal {
+    Str(String),
+    Num(Number),
+}
+
+impl Select {
+    /// Parses a single `SELECT` statement
+    ///
+    /// # Errors
+    /// If the statement isn't valid in this dialect.
+    #[inline]
+    pub fn parse(sql: &str) -> core::result::Result<Self, SqlError> {
+        Parser {
+            tokens: lex(sql)?,
+            pos: 0,
+        }
+        .select()
+    }
+
+    /// The query the rows are selected with
+    #[inline]
+    #[must_use]
+    pub fn query(&self) -> Query {
+        let filter = self
+            .condition
+            .as_ref()
+            .map_or(LinkFilter::Any, |condition| condition.compile(false));
+        let query = Query::new(filter);
+        match self.limit {
+            // Sorting needs all rows
+            Some(limit) if self.order.is_empty() => query.with_limit(limit),
+            _ => query,
+        }
+        .build()
+    }
+
+    /// Selects the rows from `data`
+    ///
+    /// Rows are the matching links of the data at the `FROM` path, as shared data if all
+    /// columns are selected or as [`Row`]s of the selected columns.
+    ///
+    /// # Errors
+    /// Any error returned while querying the links.
+    #[inline]
+    pub fn execute(
+        &self,
+        data: &(impl Data + ?Sized),
+    ) -> core::result::Result<Vec<Rc<dyn Data>>, LinkError> {
+        let limit = match self.limit {
+            Some(limit) if self.order.is_empty() => limit,
+            _ => usize::MAX,
+        };
+        let mut scan = Scan {
+            select: self,
+            rows: Vec::new(),
+            limit,
+        };
+        let from = steps(&self.from);
+        match from.split_first() {
+            None => scan.scan(data)?,
+            Some((first, rest)) => data.query_links(&mut Step::new(rest, &mut scan), first)?,
+        }
+        let mut rows = scan.rows;
+
+        if !self.order.is_empty() {
+            rows = self.sort(rows)?;
+            rows.truncate(self.limit.unwrap_or(usize::MAX));
+        }
+
+        let Some(columns) = &self.columns else {
+            return Ok(rows);
+        };
+        let names: Rc<[String]> = columns.iter().map(|c| c.name.clone()).collect();
+        let paths: Vec<_> = columns.iter().map(|c| steps(&c.path)).collect();
+        rows.iter()
+            .map(|row| {
+                let values = paths
+                    .iter()
+                    .map(|path| node_at(row.as_ref(), path))
+                    .collect::<core::result::Result<_, _>>()?;
+                let row = Row {
+                    columns: Rc::clone(&names),
+                    values,
+                };
+                Ok(Rc::new(row) as Rc<dyn Data>)
+            })
+            .collect()
+    }
+
+    fn sort(&self, rows: Vec<Rc<dyn Data>>) -> core::result::Result<Vec<Rc<dyn Data>>, LinkError> {
+        let paths: Vec<_> = self.order.iter().map(|(path, _)| steps(path)).collect();
+        let mut keyed = rows
+            .into_iter()
+            .map(|row| {
+                let keys = paths
+                    .iter()
+                    .map(|path| value_at(row.as_ref(), path))
+                    .collect::<core::result::Result<Vec<_>, _>>()?;
+                Ok((keys, row))
+            })
+            .collect::<core::result::Result<Vec<_>, LinkError>>()?;
+
+        keyed.sort_by(|(a, _), (b, _)| {
+            let directions = self.order.iter().map(|(_, direction)| direction);
+            for ((a, b), direction) in a.iter().zip(b).zip(directions) {
+                let ordering = match direction {
+                    Direction::Asc => a.cmp(b),
+                    Direction::Desc => b.cmp(a),
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            Ordering::Equal
+        });
+        Ok(keyed.into_iter().map(|(_, row)| row).collect())
+    }
+}
+
+impl FromStr for Select {
+    type Err = SqlError;
+
+    #[inline]
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// A row of selected columns, linking from each column name to its value
+#[derive(Debug)]
+pub struct Row {
+    columns: Rc<[String]>,
+    values: Vec<Option<Rc<dyn Data>>>,
+}
+
+impl Row {
+    /// The value of a column, `None` if the column is missing or has no value in this row
+    #[inline]
+    #[must_use]
+    pub fn get(&self, column: &str) -> Option<&dyn Data> {
+        let index = self.columns.iter().position(|c| c == column)?;
+        self.values[index].as_deref()
+    }
+}
+
+impl Data for Row {
+    #[inline]
+    fn provide_links(&self, links: &mut dyn Links) -> core::result::Result<(), LinkError> {
+        for (column, value) in self.columns.iter().zip(&self.values) {
+            let Some(value) = value else {
+                continue;
+            };
+            if links
+                .push_keyed(Box::new(Rc::clone(value)), Box::new(column.clone()))?
+                .is_break()
+            {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Collects the rows of each data found at the `FROM` path
+struct Scan<'s> {
+    select: &'s Select,
+    rows: Vec<Rc<dyn Data>>,
+    limit: usize,
+}
+
+impl Scan<'_> {
+    fn scan(&mut self, source: &(impl Data + ?Sized)) -> core::result::Result<(), LinkError> {
+        Plan::new(self.select.query(), source).execute(source, &mut Rows(self))
+    }
+}
+
+impl Links for Scan<'_> {
+    #[inline]
+    fn push(&mut self, target: BoxedData, _key: Option<BoxedData>) -> Result {
+        if self.rows.len() >= self.limit {
+            return BREAK;
+        }
+        self.scan(target.as_ref())?;
+        CONTINUE
+    }
+}
+
+/// Adds rows to a [`Scan`] until its limit is reached
+struct Rows<'a, 's>(&'a mut Scan<'s>);
+
+impl Links for Rows<'_, '_> {
+    #[inline]
+    fn push(&mut self, target: BoxedData, _key: Option<BoxedData>) -> Result {
+        let scan = &mut *self.0;
+        if scan.rows.len() >= scan.limit {
+            return BREAK;
+        }
+        scan.rows.push(target.into());
+        if scan.rows.len() >= scan.limit {
+            BREAK
+        } else {
+            CONTINUE
+        }
+    }
+}
+
+impl Condition {
+    /// The row filter, negated if `negated`
+    ///
+    /// Negations are pushed down to the predicates, which like in SQL only match rows with a
+    /// value of the compared kind at their path. So `NOT age < 18` and `age != 5` skip rows
+    /// without an age and rows whose age isn't a number. `IS NULL` matches rows without a value.
+    fn compile(&self, negated: bool) -> LinkFilter {
+        let compile = |c: &Self| c.compile(negated);
+        match self {
+            Condition::And(all) if negated => {
+                LinkFilter::Or(all.iter().map(compile).collect::<Vec<_>>().into())
+            }
+            Condition::And(all) => {
+                LinkFilter::And(all.iter().map(compile).collect::<Vec<_>>().into())
+            }
+            Condition::Or(any) if negated => {
+                LinkFilter::And(any.iter().map(compile).collect::<Vec<_>>().into())
+            }
+            Condition::Or(any) => {
+                LinkFilter::Or(any.iter().map(compile).collect::<Vec<_>>().into())
+            }
+            Condition::Not(c) => c.compile(!negated),
+            Condition::Compare(path, op, literal) => {
+                let negated = negated != matches!(op, Op::Ne);
+                at(path, kind(literal), compare(*op, literal), negated)
+            }
+            Condition::Like(path, prefix) => at(
+                path,
+                DataFilter::is_string(),
+                DataFilter::prefix(prefix.as_str()),
+                negated,
+            ),
+            Condition::Between(path, min, max) => at(
+                path,
+                DataFilter::is_number(),
+                DataFilter::between(*min, *max),
+                negated,
+            ),
+            Condition::In(path, literals) => {
+                let kinds: Vec<_> = literals.iter().map(kind).collect();
+                let any: Vec<_> = literals.iter().map(|l| compare(Op::Eq, l)).collect();
+                at(
+                    path,
+                    DataFilter::Or(kinds.into()),
+                    DataFilter::Or(any.into()),
+                    negated,
+                )
+            }
+            Condition::NotNull(path) => {
+                let not_null = LinkFilter::target(linked_at(path, !DataFilter::is_null()));
+                if negated {
+                    !not_null
+                } else {
+                    not_null
+                }
+            }
+        }
+    }
+}
+
+/// A row filter matching if a value of `kind` at `path` matches `filter`, or not if `negated`
+fn at(path: &[String], kind: DataFilter, filter: DataFilter, negated: bool) -> LinkFilter {
+    let filter = if negated { kind & !filter } else { filter };
+    LinkFilter::target(linked_at(path, filter))
+}
+
+/// The values `literal` can be compared to
+fn kind(literal: &Literal) -> DataFilter {
+    match literal {
+        Literal::Num(_) => DataFilter::is_number(),
+        Literal::Str(_) => DataFilter::is_string(),
+    }
+}
+
+/// The filter for a comparison with `literal`, `Op::Ne` is compiled as a negated `Op::Eq`
+fn compare(op: Op, literal: &Literal) -> DataFilter {
+    match literal {
+        Literal::Num(n) => match op {
+            Op::Eq | Op::Ne => DataFilter::eq_num(*n),
+            Op::Lt => DataFilter::lt(*n),
+            Op::Le => DataFilter::le(*n),
+            Op::Gt => DataFilter::gt(*n),
+            Op::Ge => DataFilter::ge(*n),
+        },
+        Literal::Str(s) => {
+            let bound = |included: bool| {
+                if included {
+                    Bound::Included(s.as_str())
+                } else {
+                    Bound::Excluded(s.as_str())
+                }
+            };
+            match op {
+                Op::Eq | Op::Ne => DataFilter::exact(s.as_str()),
+                Op::Lt => DataFilter::str_range::<&str>((Bound::Unbounded, bound(false))),
+                Op::Le => DataFilter::str_range::<&str>((Bound::Unbounded, bound(true))),
+                Op::Gt => DataFilter::str_range::<&str>((bound(false), Bound::Unbounded)),
+                Op::Ge => DataFilter::str_range::<&str>((bound(true), Bound::Unbounded)),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Identifier or keyword
+    Word(String),
+    /// Identifier in double quotes
+    Quoted(String),
+    Str(String),
+    Num(String),
+    Symbol(&'static str),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Word(w) | Token::Num(w) => write!(f, "{w}"),
+            Token::Quoted(q) => write!(f, "\"{q}\""),
+            Token::Str(s) => write!(f, "'{s}'"),
+            Token::Symbol(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+const SYMBOLS: [&str; 13] = [
+    "<=", ">=", "<>", "!=", "=", "<", ">", "*", ",", ".", "(", ")", ";",
+];
+
+fn lex(sql: &str) -> core::result::Result<Vec<(usize, Token)>, SqlError> {
+    let mut tokens = Vec::new();
+    let mut rest = sql;
+    loop {
+        rest = rest.trim_start();
+        let position = sql.len() - rest.len();
+        let Some(c) = rest.chars().next() else {
+            return Ok(tokens);
+        };
+
+        let (token, len) = if c == '\'' || c == '"' {
+            let (text, len) = quoted(rest, c).ok_or(SqlError::UnterminatedQuote(position))?;
+            let token = if c == '"' {
+                Token::Quoted(text)
+            } else {
+                Token::Str(text)
+            };
+            (token, len)
+        } else if c.is_ascii_digit()
+            || (c == '-' && rest[1..].starts_with(|c: char| c.is_ascii_digit()))
+        {
+            let len = 1 + rest[1..]
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .unwrap_or(rest.len() - 1);
+            (Token::Num(rest[..len].to_owned()), len)
+        } else if c.is_alphabetic() || c == '_' {
+            let len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            (Token::Word(rest[..len].to_owned()), len)
+        } else if let Some(symbol) = SYMBOLS.into_iter().find(|s| rest.starts_with(s)) {
+            (Token::Symbol(symbol), symbol.len())
+        } else {
+            return Err(SqlError::Unexpected {
+                found: c.to_string(),
+                position,
+                expected: "a token",
+            });
+        };
+        tokens.push((position, token));
+        rest = &rest[len..];
+    }
+}
+
+/// Text in quotes, where a doubled quote is an escaped quote, and the length including the quotes
+fn quoted(s: &str, quote: char) -> Option<(String, usize)> {
+    let mut text = String::new();
+    let mut chars = s.char_indices().skip(1).peekable();
+    while let Some((i, c)) = chars.next() {
+        if c != quote {
+            text.push(c);
+        } else if chars.next_if(|&(_, c)| c == quote).is_some() {
+            text.push(quote);
+        } else {
+            return Some((text, i + 1));
+        }
+    }
+    None
+}
+
+const RESERVED: [&str; 17] = [
+    "SELECT", "FROM", "WHERE", "ORDER", "BY", "ASC", "DESC", "LIMIT", "AS", "AND", "OR", "NOT",
+    "LIKE", "BETWEEN", "IN", "IS", "NULL",
+];
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, t)| t)
+    }
+
+    fn unexpected(&self, expected: &'static str) -> SqlError {
+        match self.tokens.get(self.pos) {
+            Some((position, token)) => SqlError::Unexpected {
+                found: token.to_string(),
+                position: *position,
+                expected,
+            },
+            None => SqlError::UnexpectedEnd(expected),
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(w)) if w.eq_ignore_ascii_case(keyword))
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+        self.pos += usize::from(found);
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &'static str) -> core::result::Result<(), SqlError> {
+        if self.keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.unexpected(keyword))
+        }
+    }
+
+    fn symbol(&mut self, symbol: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Symbol(s)) if *s == symbol);
+        self.pos += usize::from(found);
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: &'static str) -> core::result::Result<(), SqlError> {
+        if self.symbol(symbol) {
+            Ok(())
+        } else {
+            Err(self.unexpected(symbol))
+        }
+    }
+
+    fn select(mut self) -> core::result::Result<Select, SqlError> {
+        self.expect_keyword("SELECT")?;
+        let columns = if self.symbol("*") {
+            None
+        } else {
+            Some(self.list(Self::column)?)
+        };
+        let from = if self.keyword("FROM") {
+            self.path()?
+        } else {
+            Vec::new()
+        };
+        let condition = if self.keyword("WHERE") {
+            Some(self.or()?)
+        } else {
+            None
+        };
+        let order = if self.keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            self.list(Self::order)?
+        } else {
+            Vec::new()
+        };
+        let limit = if self.keyword("LIMIT") {
+            match self.peek() {
+                Some(Token::Num(n)) => {
+                    let limit = n.parse().map_err(|_| SqlError::InvalidNumber(n.clone()))?;
+                    self.pos += 1;
+                    Some(limit)
+                }
+                _ => return Err(self.unexpected("a limit")),
+            }
+        } else {
+            None
+        };
+        self.symbol(";");
+        if self.peek().is_some() {
+            return Err(self.unexpected("end of input"));
+        }
+
+        Ok(Select {
+            columns,
+            from,
+            condition,
+            order,
+            limit,
+        })
+    }
+
+    fn list<T>(
+        &mut self,
+        item: impl Fn(&mut Self) -> core::result::Result<T, SqlError>,
+    ) -> core::result::Result<Vec<T>, SqlError> {
+        let mut items = vec![item(self)?];
+        while self.symbol(",") {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    fn name(&mut self) -> core::result::Result<String, SqlError> {
+        let name = match self.peek() {
+            Some(Token::Quoted(q)) => q.clone(),
+            Some(Token::Word(w)) if !RESERVED.iter().any(|r| w.eq_ignore_ascii_case(r)) => {
+                w.clone()
+            }
+            _ => return Err(self.unexpected("a name")),
+        };
+        self.pos += 1;
+        Ok(name)
+    }
+
+    fn path(&mut self) -> core::result::Result<Vec<String>, SqlError> {
+        let mut path = vec![self.name()?];
+        while self.symbol(".") {
+            path.push(self.name()?);
+        }
+        Ok(path)
+    }
+
+    fn column(&mut self) -> core::result::Result<Column, SqlError> {
+        let path = self.path()?;
+        let name = if self.keyword("AS") {
+            self.name()?
+        } else {
+            path.last().cloned().unwrap_or_default()
+        };
+        Ok(Column { path, name })
+    }
+
+    fn order(&mut self) -> core::result::Result<(Vec<String>, Direction), SqlError> {
+        let path = self.path()?;
+        let direction = if self.keyword("DESC") {
+            Direction::Desc
+        } else {
+            self.keyword("ASC");
+            Direction::Asc
+        };
+        Ok((path, direction))
+    }
+
+    fn or(&mut self) -> core::result::Result<Condition, SqlError> {
+        let mut any = vec![self.and()?];
+        while self.keyword("OR") {
+            any.push(self.and()?);
+        }
+        Ok(if any.len() == 1 {
+            any.remove(0)
+        } else {
+            Condition::Or(any)
+        })
+    }
+
+    fn and(&mut self) -> core::result::Result<Condition, SqlError> {
+        let mut all = vec![self.unary()?];
+        while self.keyword("AND") {
+            all.push(self.unary()?);
+        }
+        Ok(if all.len() == 1 {
+            all.remove(0)
+        } else {
+            Condition::And(all)
+        })
+    }
+
+    fn unary(&mut self) -> core::result::Result<Condition, SqlError> {
+        if self.keyword("NOT") {
+            return Ok(Condition::Not(Box::new(self.unary()?)));
+        }
+        if self.symbol("(") {
+            let condition = self.or()?;
+            self.expect_symbol(")")?;
+            return Ok(condition);
+        }
+        self.predicate()
+    }
+
+    fn predicate(&mut self) -> core::result::Result<Condition, SqlError> {
+        let path = self.path()?;
+
+        if self.keyword("IS") {
+            let negated = self.keyword("NOT");
+            self.expect_keyword("NULL")?;
+            let not_null = Condition::NotNull(path);
+            return Ok(if negated {
+                not_null
+            } else {
+                Condition::Not(Box::new(not_null))
+            });
+        }
+
+        let negated = self.keyword("NOT");
+        let condition = if self.keyword("LIKE") {
+            match self.literal()? {
+                Literal::Str(pattern) => like(path, pattern)?,
+                Literal::Num(_) => return Err(self.unexpected_previous("a pattern")),
+            }
+        } else if self.keyword("BETWEEN") {
+            let min = self.number()?;
+            self.expect_keyword("AND")?;
+            let max = self.number()?;
+            Condition::Between(path, min, max)
+        } else if self.keyword("IN") {
+            self.expect_symbol("(")?;
+            let literals = self.list(Self::literal)?;
+            self.expect_symbol(")")?;
+            Condition::In(path, literals)
+        } else if negated {
+            return Err(self.unexpected("LIKE, BETWEEN or IN"));
+        } else {
+            let op = self.op()?;
+            Condition::Compare(path, op, self.literal()?)
+        };
+
+        Ok(if negated {
+            Condition::Not(Box::new(condition))
+        } else {
+            condition
+        })
+    }
+
+    fn unexpected_previous(&mut self, expected: &'static str) -> SqlError {
+        self.pos -= 1;
+        self.unexpected(expected)
+    }
+
+    fn op(&mut self) -> core::result::Result<Op, SqlError> {
+        let op = match self.peek() {
+            Some(Token::Symbol("=")) => Op::Eq,
+            Some(Token::Symbol("!=" | "<>")) => Op::Ne,
+            Some(Token::Symbol("<")) => Op::Lt,
+            Some(Token::Symbol("<=")) => Op::Le,
+            Some(Token::Symbol(">")) => Op::Gt,
+            Some(Token::Symbol(">=")) => Op::Ge,
+            _ => return Err(self.unexpected("a comparison")),
+        };
+        self.pos += 1;
+        Ok(op)
+    }
+
+    fn literal(&mut self) -> core::result::Result<Literal, SqlError> {
+        let literal = match self.peek() {
+            Some(Token::Str(s)) => Literal::Str(s.clone()),
+            Some(Token::Num(n)) => Literal::Num(number(n)?),
+            _ => return Err(self.unexpected("a string or number")),
+        };
+        self.pos += 1;
+        Ok(literal)
+    }
+
+    fn number(&mut self) -> core::result::Result<Number, SqlError> {
+        match self.literal()? {
+            Literal::Num(n) => Ok(n),
+            Literal::Str(_) => Err(self.unexpected_previous("a number")),
+        }
+    }
+}
+
+fn number(n: &str) -> core::result::Result<Number, SqlError> {
+    let invalid = || SqlError::InvalidNumber(n.to_owned());
+    if n.contains('.') {
+        n.parse::<f64>().map(Number::from).map_err(|_| invalid())
+    } else if let Ok(i) = n.parse::<i128>() {
+        Ok(Number::from(i))
+    } else {
+        n.parse::<u128>().map(Number::from).map_err(|_| invalid())
+    }
+}
+
+fn like(path: Vec<String>, pattern: String) -> core::result::Result<Condition, SqlError> {
+    let is_wildcard = |c: char| c == '%' || c == '_';
+    match pattern.find(is_wildcard) {
+        None => Ok(Condition::Compare(path, Op::Eq, Literal::Str(pattern))),
+        Some(i) if i == pattern.len() - 1 && pattern.ends_with('%') => {
+            let mut prefix = pattern;
+            prefix.pop();
+            Ok(Condition::Like(path, prefix))
+        }
+        Some(_) => Err(SqlError::UnsupportedPattern(pattern)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::DataExt;
+    use std::collections::HashMap;
+
+    fn people() -> Vec<HashMap<&'static str, Rc<dyn Data>>> {
+        let person = |name: &'static str, age: u8, city: &'static str| {
+            HashMap::from([
+                ("name", Rc::new(name) as Rc<dyn Data>),
+                ("age", Rc::new(age)),
+                ("address", Rc::new(HashMap::from([("city", city)]))),
+            ])
+        };
+        vec![
+            person("ada", 36, "london"),
+            person("bob", 17, "paris"),
+            person("cyd", 52, "lyon"),
+            person("dan", 25, "paris"),
+        ]
+    }
+
+    fn names(rows: &[Rc<dyn Data>]) -> Vec<String> {
+        rows.iter()
+            .map(|row| {
+                let items = row.as_items().unwrap();
+                let (_, name) = items
+                    .iter()
+                    .find(|(key, _)| key.as_str().as_deref() == Some("name"))
+                    .unwrap();
+                name.as_str().unwrap()
+            })
+            .collect()
+    }
+
+    fn select(sql: &str) -> Vec<String> {
+        let select = Select::parse(sql).unwrap();
+        names(&select.execute(&people()).unwrap())
+    }
+
+    #[test]
+    fn filters() {
+        assert_eq!(
+            select("SELECT * WHERE age >= 36 ORDER BY name"),
+            ["ada", "cyd"]
+        );
+        assert_eq!(
+            select("select name where age < 20 or name = 'dan' order by age"),
+            ["bob", "dan"]
+        );
+        assert_eq!(
+            select("SELECT name WHERE address.city = 'paris' AND NOT age < 18"),
+            ["dan"]
+        );
+        assert_eq!(select("SELECT name WHERE name LIKE 'c%'"), ["cyd"]);
+        assert_eq!(
+            select("SELECT name WHERE age BETWEEN 20 AND 40 ORDER BY age DESC"),
+            ["ada", "dan"]
+        );
+        assert_eq!(
+            select("SELECT name WHERE name IN ('bob', 'eve') ;"),
+            ["bob"]
+        );
+        assert_eq!(
+            select("SELECT name WHERE name > 'c' ORDER BY name"),
+            ["cyd", "dan"]
+        );
+        assert!(select("SELECT name WHERE nickname IS NOT NULL").is_empty());
+        assert_eq!(select("SELECT name WHERE nickname IS NULL").len(), 4);
+        assert_eq!(select("SELECT name WHERE NOT name IS NULL").len(), 4);
+    }
+
+    #[test]
+    fn null_semantics() {
+        let rows = vec![
+            HashMap::from([
+                ("name", Rc::new("ada") as Rc<dyn Data>),
+                ("age", Rc::new(5u8)),
+            ]),
+            HashMap::from([
+                ("name", Rc::new("bob") as Rc<dyn Data>),
+                ("age", Rc::new(7u8)),
+            ]),
+            HashMap::from([
+                ("name", Rc::new("cyd") as Rc<dyn Data>),
+                ("age", Rc::new("five")),
+            ]),
+            HashMap::from([("name", Rc::new("dan") as Rc<dyn Data>)]),
+        ];
+        let select = |sql: &str| names(&Select::parse(sql).unwrap().execute(&rows).unwrap());
+
+        assert_eq!(select("SELECT name WHERE age != 5"), ["bob"]);
+        assert_eq!(select("SELECT name WHERE NOT age < 6"), ["bob"]);
+        assert_eq!(
+            select("SELECT name WHERE NOT (age = 5 OR age = 7)"),
+            [] as [&str; 0]
+        );
+        assert_eq!(select("SELECT name WHERE NOT age NOT IN (5)"), ["ada"]);
+        assert_eq!(select("SELECT name WHERE age <> 'five'"), [] as [&str; 0]);
+        assert_eq!(select("SELECT name WHERE age IS NULL"), ["dan"]);
+        assert!(Select::parse("SELECT null FROM is").is_err());
+    }
+
+    #[test]
+    fn order_and_limit() {
+        assert_eq!(
+            select("SELECT name ORDER BY address.city, age DESC LIMIT 3"),
+            ["ada", "cyd", "dan"]
+        );
+        assert_eq!(select("SELECT * LIMIT 2").len(), 2);
+    }
+
+    #[test]
+    fn projection() {
+        let select = Select::parse(r#"SELECT name AS "who", address.city FROM people"#).unwrap();
+        let data = HashMap::from([("people", people())]);
+        let rows = select.execute(&data).unwrap();
+        assert_eq!(rows.len(), 4);
+
+        let items = rows[0].as_items().unwrap();
+        assert_eq!(items.len(), 2);
+        let keys: Vec<_> = items.iter().map(|(key, _)| key.as_str().unwrap()).collect();
+        assert!(keys.contains(&"who".to_owned()) && keys.contains(&"city".to_owned()));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(matches!(
+            Select::parse("SELECT"),
+            Err(SqlError::UnexpectedEnd(_))
+        ));
+        assert!(matches!(
+            Select::parse("SELECT * WHERE a = 'b"),
+            Err(SqlError::UnterminatedQuote(19))
+        ));
+        assert!(matches!(
+            Select::parse("SELECT * WHERE a LIKE '%b'"),
+            Err(SqlError::UnsupportedPattern(_))
+        ));
+        assert!(matches!(
+            Select::parse("SELECT * LIMIT 1 2"),
+            Err(SqlError::Unexpected { position: 17, .. })
+        ));
+        assert!(Select::parse("SELECT from").is_err());
+    }
+
+    #[test]
+    fn pushdown() {
+        use crate::data::indexed::{IndexKind, Indexed};
+
+        let indexed = Indexed::new(people())
+            .unwrap()
+            .with_index(&["age"], IndexKind::Sorted)
+            .unwrap();
+        let select = Select::parse("SELECT name WHERE age > 30 ORDER BY name").unwrap();
+
+        let plan = Plan::new(select.query(), &indexed);
+        assert!(matches!(plan.residual(), LinkFilter::Any));
+        assert_eq!(names(&select.execute(&indexed).unwrap()), ["ada", "cyd"]);
+    }
+}
@@ -0,0 +1,35 @@
+use crate::rr::{typeset, Receiver};
+
+/// Receiver checking whether any provided string satisfies a predicate
+pub(super) struct Matcher<F> {
+    predicate: F,
+    matched: bool,
+}
+
+impl<F: Fn(&str) -> bool> Matcher<F> {
+    #[inline]
+    pub(super) const fn new(predicate: F) -> Self {
+        Self {
+            predicate,
+            matched: false,
+        }
+    }
+
+    #[inline]
+    pub(super) const fn matched(&self) -> bool {
+        self.matched
+    }
+}
+
+impl<F: Fn(&str) -> bool> Receiver for Matcher<F> {
+    #[inline]
+    fn str(&mut self, value: &str) {
+        if !self.matched && (self.predicate)(value) {
+            self.matched = true;
+        }
+    }
+    #[inline]
+    fn accepting() -> impl typeset::TypeSet + 'static {
+        typeset::STRING_LIKE
+    }
+}
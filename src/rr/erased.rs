@@ -121,7 +121,10 @@ pub struct Erased<'q>(PhantomData<&'q ()>);
 
 impl<'q> super::query::Query for Erased<'q> {
     type Request = &'q mut dyn ReceiverExt;
-    type Receiver<'r> = ErasedReceiver<'r>;
+    type Receiver<'r>
+        = ErasedReceiver<'r>
+    where
+        Self: 'r;
     type Requesting<'r> = ErasedAccepting<'r>;
 
     #[inline]
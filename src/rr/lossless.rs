@@ -0,0 +1,234 @@
+//! Receiving numbers as another numeric type, as long as no information is lost
+
+use super::Receiver;
+
+/// Primitive numeric types which can be converted into losslessly
+pub trait Numeric: Sized + Copy + 'static {
+    fn from_i128(value: i128) -> Option<Self>;
+    fn from_u128(value: u128) -> Option<Self>;
+    fn from_f64(value: f64) -> Option<Self>;
+}
+
+/// `2^127` and `2^128`, the first floats that are out of range for `i128` and `u128`
+const I128_END: f64 = 170_141_183_460_469_231_731_687_303_715_884_105_728.0;
+const U128_END: f64 = 340_282_366_920_938_463_463_374_607_431_768_211_456.0;
+
+macro_rules! impl_numeric_int {
+    ($($ty:ty),*) => {
+        $(
+            impl Numeric for $ty {
+                #[inline]
+                fn from_i128(value: i128) -> Option<Self> {
+                    value.try_into().ok()
+                }
+                #[inline]
+                fn from_u128(value: u128) -> Option<Self> {
+                    value.try_into().ok()
+                }
+                #[inline]
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                fn from_f64(value: f64) -> Option<Self> {
+                    if value.fract() != 0.0 || !value.is_finite() {
+                        return None;
+                    }
+                    if value < 0.0 {
+                        if value < -I128_END {
+                            return None;
+                        }
+                        Self::from_i128(value as i128)
+                    } else {
+                        if value >= U128_END {
+                            return None;
+                        }
+                        Self::from_u128(value as u128)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_int!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize);
+
+macro_rules! impl_numeric_float {
+    ($ty:ty) => {
+        impl Numeric for $ty {
+            #[inline]
+            #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+            fn from_i128(value: i128) -> Option<Self> {
+                let float = value as $ty;
+                // `as i128` saturates, so 2^127 would compare equal to `i128::MAX`
+                if f64::from(float) >= I128_END || float as i128 != value {
+                    return None;
+                }
+                Some(float)
+            }
+            #[inline]
+            #[allow(
+                clippy::cast_possible_truncation,
+                clippy::cast_precision_loss,
+                clippy::cast_sign_loss
+            )]
+            fn from_u128(value: u128) -> Option<Self> {
+                let float = value as $ty;
+                if f64::from(float) >= U128_END || float as u128 != value {
+                    return None;
+                }
+                Some(float)
+            }
+            #[inline]
+            #[allow(clippy::cast_possible_truncation, clippy::float_cmp)]
+            fn from_f64(value: f64) -> Option<Self> {
+                let float = value as $ty;
+                if f64::from(float) == value || value.is_nan() {
+                    Some(float)
+                } else {
+                    None
+                }
+            }
+        }
+    };
+}
+
+impl_numeric_float!(f32);
+impl_numeric_float!(f64);
+
+/// Receiver accepting any number which can be converted to `T` without loss
+///
+/// Integers are widened or converted between signed and unsigned if they fit,
+/// floats are only accepted as integers if they are integral. The first fitting value is kept.
+///
+/// ```rust
+/// use datalink::rr::{lossless::Lossless, Request};
+/// use datalink::data::Data;
+///
+/// let mut request = Request::<Lossless<u64>>::default();
+/// 42i32.provide_value(&mut request.as_erased());
+///
+/// assert_eq!(request.take().into_inner(), Some(42));
+/// ```
+#[derive(Debug)]
+pub struct Lossless<T>(Option<T>);
+
+impl<T> Lossless<T> {
+    #[inline]
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<T> Default for Lossless<T> {
+    #[inline]
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<T: Numeric> Lossless<T> {
+    #[inline]
+    fn set(&mut self, value: Option<T>) {
+        if self.0.is_none() {
+            self.0 = value;
+        }
+    }
+}
+
+impl<T: Numeric> Receiver for Lossless<T> {
+    #[inline]
+    fn i8(&mut self, value: i8) {
+        self.set(T::from_i128(value.into()));
+    }
+    #[inline]
+    fn u8(&mut self, value: u8) {
+        self.set(T::from_u128(value.into()));
+    }
+    #[inline]
+    fn i16(&mut self, value: i16) {
+        self.set(T::from_i128(value.into()));
+    }
+    #[inline]
+    fn u16(&mut self, value: u16) {
+        self.set(T::from_u128(value.into()));
+    }
+    #[inline]
+    fn i32(&mut self, value: i32) {
+        self.set(T::from_i128(value.into()));
+    }
+    #[inline]
+    fn u32(&mut self, value: u32) {
+        self.set(T::from_u128(value.into()));
+    }
+    #[inline]
+    fn i64(&mut self, value: i64) {
+        self.set(T::from_i128(value.into()));
+    }
+    #[inline]
+    fn u64(&mut self, value: u64) {
+        self.set(T::from_u128(value.into()));
+    }
+    #[inline]
+    fn i128(&mut self, value: i128) {
+        self.set(T::from_i128(value));
+    }
+    #[inline]
+    fn u128(&mut self, value: u128) {
+        self.set(T::from_u128(value));
+    }
+    #[inline]
+    fn f32(&mut self, value: f32) {
+        self.set(T::from_f64(value.into()));
+    }
+    #[inline]
+    fn f64(&mut self, value: f64) {
+        self.set(T::from_f64(value));
+    }
+    #[inline]
+    fn accepting() -> impl super::TypeSet + 'static
+    where
+        Self: Sized,
+    {
+        super::typeset::NUMERIC
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        assert_eq!(u64::from_i128(42), Some(42));
+        assert_eq!(u64::from_i128(-1), None);
+        assert_eq!(i8::from_u128(128), None);
+        assert_eq!(i128::from_u128(u128::MAX), None);
+        assert_eq!(usize::from_i128(7), Some(7));
+    }
+
+    #[test]
+    fn floats_to_integers() {
+        assert_eq!(u8::from_f64(3.0), Some(3));
+        assert_eq!(u8::from_f64(3.5), None);
+        assert_eq!(u8::from_f64(256.0), None);
+        assert_eq!(i32::from_f64(-2.0), Some(-2));
+        assert_eq!(u32::from_f64(f64::NAN), None);
+        assert_eq!(u32::from_f64(f64::INFINITY), None);
+        assert_eq!(u128::from_f64(U128_END), None);
+        assert_eq!(i128::from_f64(-I128_END), Some(i128::MIN));
+    }
+
+    #[test]
+    fn integers_to_floats() {
+        assert_eq!(f64::from_i128(1 << 53), Some(9_007_199_254_740_992.0));
+        assert_eq!(f64::from_i128((1 << 53) + 1), None);
+        assert_eq!(f32::from_u128(16_777_217), None);
+        assert_eq!(f64::from_i128(i128::MAX), None);
+        assert_eq!(f64::from_u128(u128::MAX), None);
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(f32::from_f64(0.5), Some(0.5));
+        assert_eq!(f32::from_f64(0.1), None);
+        assert!(f32::from_f64(f64::NAN).is_some_and(f32::is_nan));
+    }
+}
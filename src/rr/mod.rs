@@ -1,4 +1,5 @@
 pub mod erased;
+pub mod lossless;
 pub mod meta;
 pub mod provided;
 pub mod query;
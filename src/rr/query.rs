@@ -5,7 +5,9 @@ use super::{
 
 pub trait Query {
     type Request;
-    type Receiver<'r>: Receiver;
+    type Receiver<'r>: Receiver
+    where
+        Self: 'r;
     type Requesting<'r>: TypeSet;
 
     fn get_receiver(request: &mut Self::Request) -> Self::Receiver<'_>;
@@ -18,7 +20,10 @@ pub struct IgnoreMeta<Q: Query>(core::marker::PhantomData<Q>);
 
 impl<Q: Query> Query for IgnoreMeta<Q> {
     type Request = Q::Request;
-    type Receiver<'r> = Q::Receiver<'r>;
+    type Receiver<'r>
+        = Q::Receiver<'r>
+    where
+        Self: 'r;
     type Requesting<'r> = typeset::And<Q::Requesting<'r>, typeset::Not<super::meta::MetaTypes>>;
 
     #[inline]
@@ -50,3 +55,28 @@ impl<R: Receiver + 'static> Query for R {
         typeset::AcceptedBy::new()
     }
 }
+
+/// Query answered by a borrowed receiver
+///
+/// Unlike receivers used as their own query, the receiver doesn't have to be `'static`.
+#[derive(Debug)]
+pub struct Borrowed<'q, R: ?Sized>(core::marker::PhantomData<&'q mut R>);
+
+impl<'q, R: Receiver> Query for Borrowed<'q, R> {
+    type Request = &'q mut R;
+    type Receiver<'r>
+        = &'r mut R
+    where
+        Self: 'r;
+    type Requesting<'r> = typeset::AcceptedBy<R>;
+
+    #[inline]
+    fn get_receiver(request: &mut Self::Request) -> Self::Receiver<'_> {
+        request
+    }
+
+    #[inline]
+    fn get_requesting(_request: &Self::Request) -> Self::Requesting<'_> {
+        typeset::AcceptedBy::new()
+    }
+}
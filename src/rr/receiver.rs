@@ -316,6 +316,35 @@ impl Receiver for Option<Vec<u8>> {
     }
 }
 
+/// For types which are only provided by reference or boxed
+macro_rules! impl_option_any_receiver {
+    ($ty:ty) => {
+        impl $crate::rr::Receiver for Option<$ty> {
+            #[inline]
+            fn other_ref(&mut self, value: &dyn Any) {
+                if let Some(value) = value.downcast_ref::<$ty>() {
+                    self.replace(value.clone());
+                }
+            }
+            #[inline]
+            fn other_boxed(&mut self, value: Box<dyn Any>) {
+                if let Ok(value) = value.downcast::<$ty>() {
+                    self.replace(*value);
+                }
+            }
+            #[inline]
+            fn accepting() -> impl $crate::rr::typeset::TypeSet + 'static {
+                crate::rr::typeset::Only::<$ty>::default()
+            }
+        }
+    };
+}
+
+impl_option_any_receiver!(std::net::IpAddr);
+impl_option_any_receiver!(std::net::Ipv4Addr);
+impl_option_any_receiver!(std::net::Ipv6Addr);
+impl_option_any_receiver!(std::net::SocketAddr);
+
 pub trait ReceiverExt: Receiver {
     fn accepts_id(&self, type_id: TypeId) -> bool;
 }
@@ -229,8 +229,12 @@ impl<T: TypeSet> TypeSet for Not<T> {
     }
 }
 
-pub const STRING_LIKE: AnyOf<(String, &'static str)> = AnyOf::new();
-pub const BYTES_LIKE: AnyOf<(Vec<u8>, &'static [u8])> = AnyOf::new();
+pub type StringLike = AnyOf<(String, &'static str)>;
+pub const STRING_LIKE: StringLike = AnyOf::new();
+pub type BytesLike = AnyOf<(Vec<u8>, &'static [u8])>;
+pub const BYTES_LIKE: BytesLike = AnyOf::new();
+pub type Numeric = Or<AnyOf<(i8, u8, i16, u16, i32, u32)>, AnyOf<(i64, u64, i128, u128, f32, f64)>>;
+pub const NUMERIC: Numeric = Or(AnyOf::new(), AnyOf::new());
 
 #[derive(Debug)]
 pub struct AcceptedBy<R>(PhantomData<R>);
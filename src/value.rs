@@ -4,7 +4,17 @@ pub use crate::rr::prelude::{
     Provided, Query as ValueQuery, Receiver as ValueReceiver, Request as ValueRequest,
 };
 
+/// A single value as provided by `Data`
+///
+/// Values have a total order: first by variant, then by value.
+/// `True`, `False` and `Bool` are treated as the same variant, floats are compared with `total_cmp`.
+/// `Other` values are ordered by their [`meta`](crate::rr::meta) name,
+/// all `Other` values of types without a name are equal.
+///
+/// With the `serde` feature, all variants except `Other` can be serialized,
+/// serializing `Other` returns an error.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Value {
     True,
     False,
@@ -24,6 +34,10 @@ pub enum Value {
     Char(char),
     String(String),
     Bytes(Vec<u8>),
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "serialize_other", skip_deserializing)
+    )]
     Other(Box<dyn Any>),
 }
 
@@ -51,6 +65,15 @@ impl Value {
             _ => None,
         }
     }
+
+    /// This value with numbers of any type in one representation, so equal numbers are equal
+    ///
+    /// Used as key wherever values are grouped, joined or indexed, see [`Number`](crate::query::Number).
+    #[inline]
+    #[must_use]
+    pub fn normalized_number(self) -> Self {
+        crate::query::Number::try_from(&self).map_or(self, |number| number.normalized().into())
+    }
 }
 
 impl ValueReceiver for Value {
@@ -192,6 +215,127 @@ impl crate::Data for Value {
     }
 }
 
+impl Value {
+    /// Position of the variant in the order of values
+    const fn rank(&self) -> u8 {
+        match *self {
+            Value::True | Value::False | Value::Bool(_) => 0,
+            Value::U8(_) => 1,
+            Value::I8(_) => 2,
+            Value::U16(_) => 3,
+            Value::I16(_) => 4,
+            Value::U32(_) => 5,
+            Value::I32(_) => 6,
+            Value::U64(_) => 7,
+            Value::I64(_) => 8,
+            Value::U128(_) => 9,
+            Value::I128(_) => 10,
+            Value::F32(_) => 11,
+            Value::F64(_) => 12,
+            Value::Char(_) => 13,
+            Value::String(_) => 14,
+            Value::Bytes(_) => 15,
+            Value::Other(_) => 16,
+        }
+    }
+
+    const fn bool_value(&self) -> Option<bool> {
+        match *self {
+            Value::True => Some(true),
+            Value::False => Some(false),
+            Value::Bool(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+/// Stable key of an `Other` value, the meta name of its type
+fn other_name(value: &dyn Any) -> Option<&'static str> {
+    crate::rr::meta::MetaInfo::about_val(value).name()
+}
+
+#[cfg(feature = "serde")]
+#[allow(clippy::borrowed_box)]
+fn serialize_other<S: serde::Serializer>(value: &Box<dyn Any>, _: S) -> Result<S::Ok, S::Error> {
+    use serde::ser::Error;
+    let name = other_name(value.as_ref()).unwrap_or("unknown");
+    Err(S::Error::custom(format_args!(
+        "Value::Other ({name}) holds a type-erased value and can't be serialized"
+    )))
+}
+
+impl Ord for Value {
+    #[inline]
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        use Value::*;
+        match (self, other) {
+            (U8(a), U8(b)) => a.cmp(b),
+            (I8(a), I8(b)) => a.cmp(b),
+            (U16(a), U16(b)) => a.cmp(b),
+            (I16(a), I16(b)) => a.cmp(b),
+            (U32(a), U32(b)) => a.cmp(b),
+            (I32(a), I32(b)) => a.cmp(b),
+            (U64(a), U64(b)) => a.cmp(b),
+            (I64(a), I64(b)) => a.cmp(b),
+            (U128(a), U128(b)) => a.cmp(b),
+            (I128(a), I128(b)) => a.cmp(b),
+            (F32(a), F32(b)) => a.total_cmp(b),
+            (F64(a), F64(b)) => a.total_cmp(b),
+            (Char(a), Char(b)) => a.cmp(b),
+            (String(a), String(b)) => a.cmp(b),
+            (Bytes(a), Bytes(b)) => a.cmp(b),
+            (Other(a), Other(b)) => other_name(a.as_ref()).cmp(&other_name(b.as_ref())),
+            (a, b) => match (a.bool_value(), b.bool_value()) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                _ => a.rank().cmp(&b.rank()),
+            },
+        }
+    }
+}
+
+impl PartialOrd for Value {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Value {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Value {}
+
+impl std::hash::Hash for Value {
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.rank().hash(state);
+        match *self {
+            Value::True | Value::False | Value::Bool(_) => self.bool_value().hash(state),
+            Value::U8(v) => v.hash(state),
+            Value::I8(v) => v.hash(state),
+            Value::U16(v) => v.hash(state),
+            Value::I16(v) => v.hash(state),
+            Value::U32(v) => v.hash(state),
+            Value::I32(v) => v.hash(state),
+            Value::U64(v) => v.hash(state),
+            Value::I64(v) => v.hash(state),
+            Value::U128(v) => v.hash(state),
+            Value::I128(v) => v.hash(state),
+            // `total_cmp` only considers floats equal if their bits are
+            Value::F32(v) => v.to_bits().hash(state),
+            Value::F64(v) => v.to_bits().hash(state),
+            Value::Char(v) => v.hash(state),
+            Value::String(ref v) => v.hash(state),
+            Value::Bytes(ref v) => v.hash(state),
+            Value::Other(ref v) => other_name(v.as_ref()).hash(state),
+        }
+    }
+}
+
 impl std::fmt::Display for Value {
     #[inline]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -341,6 +485,45 @@ impl AllValues {
             None
         }
     }
+
+    /// Whether an equal value was provided
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, value: &Value) -> bool {
+        self.0.contains(value)
+    }
+
+    /// Remove all values equal to an earlier one, keeping the order of the rest
+    ///
+    /// Unlike `Vec::dedup`, this also removes duplicates which aren't adjacent.
+    ///
+    /// ```rust
+    /// use datalink::data::DataExt;
+    /// use datalink::value::Value;
+    ///
+    /// let mut values = true.all_values();
+    /// values.push(Value::True);
+    /// values.dedup();
+    ///
+    /// assert_eq!(values.len(), 1);
+    /// ```
+    #[inline]
+    pub fn dedup(&mut self) {
+        let values = &self.0;
+        // Sorting indices keeps the first of each run of equal values in front
+        let mut order: Vec<usize> = (0..values.len()).collect();
+        order.sort_by(|&a, &b| values[a].cmp(&values[b]).then(a.cmp(&b)));
+
+        let mut keep = vec![true; values.len()];
+        for pair in order.windows(2) {
+            if values[pair[0]] == values[pair[1]] {
+                keep[pair[1]] = false;
+            }
+        }
+
+        let mut keep = keep.into_iter();
+        self.0.retain(|_| keep.next().unwrap_or(true));
+    }
 }
 
 impl core::ops::Deref for AllValues {
@@ -368,3 +551,111 @@ impl IntoIterator for AllValues {
         self.0.into_iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn bools_unified() {
+        assert_eq!(Value::True, Value::Bool(true));
+        assert_eq!(Value::False, Value::Bool(false));
+        assert!(Value::False < Value::True);
+
+        let set: HashSet<Value> = [Value::True, Value::Bool(true)].into_iter().collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn total_order() {
+        let mut values = [
+            Value::String("a".into()),
+            Value::F64(f64::NAN),
+            Value::U8(2),
+            Value::F64(-0.0),
+            Value::U8(1),
+            Value::True,
+            Value::F64(0.0),
+        ];
+        values.sort();
+
+        assert_eq!(values[0], Value::True);
+        assert_eq!(values[1], Value::U8(1));
+        assert_eq!(values[2], Value::U8(2));
+        assert!(matches!(values[3], Value::F64(v) if v.is_sign_negative()));
+        assert!(matches!(values[4], Value::F64(v) if v.is_sign_positive()));
+        assert!(matches!(values[5], Value::F64(v) if v.is_nan()));
+        assert_eq!(values[6], Value::String("a".into()));
+    }
+
+    #[test]
+    fn variants_differ() {
+        assert_ne!(Value::U8(1), Value::U16(1));
+        assert_eq!(
+            Value::Other(Box::new(crate::rr::meta::IsNull)),
+            Value::Other(Box::new(crate::rr::meta::IsNull))
+        );
+    }
+
+    #[test]
+    fn other_by_name() {
+        use crate::rr::meta::{IsNone, IsNull};
+
+        assert_eq!(Value::Other(Box::new(1u8)), Value::Other(Box::new(2u16)));
+        assert!(Value::Other(Box::new(1u8)) < Value::Other(Box::new(IsNone)));
+        assert!(Value::Other(Box::new(IsNone)) < Value::Other(Box::new(IsNull)));
+    }
+
+    #[test]
+    fn dedup() {
+        let mut values = AllValues(vec![
+            Value::U8(2),
+            Value::U8(1),
+            Value::U8(2),
+            Value::Bool(true),
+            Value::True,
+        ]);
+        values.dedup();
+
+        assert_eq!(*values, [Value::U8(2), Value::U8(1), Value::True]);
+        assert!(values.contains(&Value::U8(1)));
+        assert!(!values.contains(&Value::U8(3)));
+    }
+
+    #[test]
+    fn normalized_number() {
+        assert_eq!(
+            Value::U32(1).normalized_number(),
+            Value::U64(1).normalized_number()
+        );
+        assert_eq!(
+            Value::F64(1.0).normalized_number(),
+            Value::I8(1).normalized_number()
+        );
+        assert_eq!(
+            Value::U128(u128::MAX).normalized_number(),
+            Value::U128(u128::MAX)
+        );
+        assert_eq!(Value::F32(0.5).normalized_number(), Value::F64(0.5));
+        assert_eq!(Value::True.normalized_number(), Value::True);
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "json"))]
+    fn serde_roundtrip() {
+        let values = [
+            Value::True,
+            Value::I64(-3),
+            Value::F32(0.5),
+            Value::String("foo".into()),
+            Value::Bytes(vec![1, 2]),
+        ];
+        let json = serde_json::to_string(&values).unwrap();
+        let back: [Value; 5] = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(values, back);
+        let err = serde_json::to_string(&Value::Other(Box::new(crate::rr::meta::IsNull)));
+        assert!(err.is_err_and(|e| e.to_string().contains("Value::Other (IsNull)")));
+    }
+}
@@ -1,60 +1,308 @@
+//! Shared vocabulary of terms, mostly used as link keys
+//!
+//! Using these instead of ad-hoc strings lets tools understand each other's data.
+
+use std::fmt::{self, Display};
+
+use crate::id::ID;
+
+pub mod registry;
+
 pub trait WellKnown {
     const ID: crate::id::ID;
 }
 
-macro_rules! impl_well_known {
-    ($val:ident, $type:ident, id=$id:literal $($args:tt)*) => {
-        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-        pub struct $type;
-        impl $crate::well_known::WellKnown for $type {
-            const ID: $crate::id::ID = unsafe { $crate::id::ID::new_unchecked($id) };
+/// A well-known term as declared with [`well_known!`](crate::well_known!)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Term {
+    id: ID,
+    namespace: &'static str,
+    name: &'static str,
+    symbol: &'static str,
+    description: Option<&'static str>,
+}
+
+impl Term {
+    #[inline]
+    #[must_use]
+    pub const fn new(
+        id: ID,
+        namespace: &'static str,
+        name: &'static str,
+        symbol: &'static str,
+        description: Option<&'static str>,
+    ) -> Self {
+        Self {
+            id,
+            namespace,
+            name,
+            symbol,
+            description,
         }
-        $crate::impl_data!($type, id=<Self as $crate::well_known::WellKnown>::ID.into_raw() $($args)*);
-        pub const $val: $type = $type;
-    };
-}
-
-impl_well_known!(NONE, NoneType, id = 0x734BFA09_662B_477C_8B61_7E85B6C47645);
-impl_well_known!(
-    TAG,
-    TagType,
-    id = 0x734BFA09_662B_477C_8B61_7E85B6C47646,
-    value = "tag"
-);
-impl_well_known!(
-    TYPE,
-    TypeType,
-    id = 0x734BFA09_662B_477C_8B61_7E85B6C47647,
-    value = "type"
-);
-impl_well_known!(
-    KEY,
-    KeyType,
-    id = 0x734BFA09_662B_477C_8B61_7E85B6C47648,
-    value = "key"
-);
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn id(&self) -> ID {
+        self.id
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn namespace(&self) -> &'static str {
+        self.namespace
+    }
+
+    /// Textual name of the term, e.g. `tag`
+    #[inline]
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Name of the constant the term was declared as, e.g. `TAG`
+    #[inline]
+    #[must_use]
+    pub const fn symbol(&self) -> &'static str {
+        self.symbol
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn description(&self) -> Option<&'static str> {
+        self.description
+    }
+
+    /// Name prefixed with the namespace, e.g. `datalink/tag`
+    #[inline]
+    #[must_use]
+    pub fn qualified_name(&self) -> String {
+        format!("{}/{}", self.namespace, self.name)
+    }
+}
+
+impl Display for Term {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol)
+    }
+}
+
+/// A namespaced set of well-known terms
+#[derive(Debug)]
+pub struct Vocabulary {
+    namespace: &'static str,
+    terms: &'static [Term],
+}
+
+impl Vocabulary {
+    #[inline]
+    #[must_use]
+    pub const fn new(namespace: &'static str, terms: &'static [Term]) -> Self {
+        Self { namespace, terms }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn namespace(&self) -> &'static str {
+        self.namespace
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn terms(&self) -> &'static [Term] {
+        self.terms
+    }
+
+    /// Look up a term by its textual name, without namespace
+    #[inline]
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&'static Term> {
+        self.terms.iter().find(|term| term.name == name)
+    }
+}
+
+crate::well_known! {
+    namespace = "datalink";
+
+    NONE: NoneType {
+        id = 0x734BFA09_662B_477C_8B61_7E85B6C47645,
+        name = "none",
+        value = none,
+        description = "Absence of a meaningful key or value"
+    },
+    TAG: TagType {
+        id = 0x734BFA09_662B_477C_8B61_7E85B6C47646,
+        name = "tag",
+        description = "Key of links to tags of the data"
+    },
+    TYPE: TypeType {
+        id = 0x734BFA09_662B_477C_8B61_7E85B6C47647,
+        name = "type",
+        description = "Key of links to the type of the data"
+    },
+    KEY: KeyType {
+        id = 0x734BFA09_662B_477C_8B61_7E85B6C47648,
+        name = "key",
+        description = "Key of links to the key of an entry"
+    },
+    /// Human readable name, e.g. of a file or a person
+    NAME: NameType {
+        id = 0x734BFA09_662B_477C_8B61_7E85B6C4764B,
+        name = "name",
+        description = "Human readable name"
+    },
+    /// Longer human readable text about the data
+    DESCRIPTION: DescriptionType {
+        id = 0x734BFA09_662B_477C_8B61_7E85B6C4764C,
+        name = "description",
+        description = "Human readable description"
+    },
+    /// The data containing this one, e.g. a directory
+    PARENT: ParentType {
+        id = 0x734BFA09_662B_477C_8B61_7E85B6C4764D,
+        name = "parent",
+        description = "Data containing this one"
+    },
+    /// Data contained in this one, the inverse of [`PARENT`]
+    CHILD: ChildType {
+        id = 0x734BFA09_662B_477C_8B61_7E85B6C4764E,
+        name = "child",
+        description = "Data contained in this one"
+    },
+    /// Point in time the data was created
+    CREATED_AT: CreatedAtType {
+        id = 0x734BFA09_662B_477C_8B61_7E85B6C4764F,
+        name = "created_at",
+        description = "Point in time of creation"
+    },
+    /// Point in time the data was last modified
+    MODIFIED_AT: ModifiedAtType {
+        id = 0x734BFA09_662B_477C_8B61_7E85B6C47650,
+        name = "modified_at",
+        description = "Point in time of the last modification"
+    },
+    /// Unit of a numeric value, e.g. `"s"` or `"B"`
+    UNIT: UnitType {
+        id = 0x734BFA09_662B_477C_8B61_7E85B6C47651,
+        name = "unit",
+        description = "Unit of a numeric value"
+    },
+    /// Language of a text as BCP 47 tag, e.g. `"en-US"`
+    LANGUAGE: LanguageType {
+        id = 0x734BFA09_662B_477C_8B61_7E85B6C47652,
+        name = "language",
+        description = "Language of a text"
+    },
+    /// Where the data was obtained from, e.g. an URL or a path
+    SOURCE: SourceType {
+        id = 0x734BFA09_662B_477C_8B61_7E85B6C47653,
+        name = "source",
+        description = "Origin of the data"
+    },
+    /// Version of the data or of its format
+    VERSION: VersionType {
+        id = 0x734BFA09_662B_477C_8B61_7E85B6C47654,
+        name = "version",
+        description = "Version of the data"
+    },
+    /// Position of the data in an ordered collection
+    INDEX: IndexType {
+        id = 0x734BFA09_662B_477C_8B61_7E85B6C47655,
+        name = "index",
+        description = "Position in an ordered collection"
+    },
+}
+
+/// Terms about files and the filesystem
+pub mod fs {
+    crate::well_known! {
+        namespace = "datalink/fs";
+
+        /// Path of a file or directory
+        PATH: PathType {
+            id = 0x734BFA09_662B_477C_8B61_7E85B6C47656,
+            name = "path",
+            description = "Path of a file or directory"
+        },
+        /// Size of a file in bytes
+        SIZE: SizeType {
+            id = 0x734BFA09_662B_477C_8B61_7E85B6C47657,
+            name = "size",
+            description = "Size in bytes"
+        },
+    }
+}
+
+/// Terms about networking
 pub mod net {
-    impl_well_known!(
-        IP,
-        IPType,
-        id = 0x734BFA09_662B_477C_8B61_7E85B6C47649,
-        value = "ip"
-    );
-    impl_well_known!(
-        PORT,
-        PortType,
-        id = 0x734BFA09_662B_477C_8B61_7E85B6C4764A,
-        value = "port"
-    );
+    crate::well_known! {
+        namespace = "datalink/net";
+
+        /// Key of links to an [`IpAddr`](std::net::IpAddr)
+        IP: IPType<std::net::IpAddr> {
+            id = 0x734BFA09_662B_477C_8B61_7E85B6C47649,
+            name = "ip",
+            description = "IP address"
+        },
+        /// Key of links to a port number
+        PORT: PortType<u16> {
+            id = 0x734BFA09_662B_477C_8B61_7E85B6C4764A,
+            name = "port",
+            description = "Port number"
+        },
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::data::{Data, DataExt};
+    use crate::query::DataFilter;
+    use filters::Filter;
     use std::mem::size_of_val;
 
     #[test]
     fn none_is_zst() {
         assert_eq!(size_of_val(&NONE), 0);
     }
+
+    #[test]
+    fn none_has_no_value() {
+        assert!(NONE.as_str().is_none());
+        assert!(!DataFilter::is_string().matches(&NONE));
+        assert_eq!(TAG.as_str().as_deref(), Some("tag"));
+    }
+
+    #[test]
+    fn vocabulary() {
+        assert_eq!(VOCABULARY.namespace(), "datalink");
+        assert_eq!(VOCABULARY.terms().len(), 15);
+
+        let tag = VOCABULARY.get("tag").unwrap();
+        assert_eq!(tag.id(), TagType::ID);
+        assert_eq!(tag.symbol(), "TAG");
+        assert_eq!(tag.qualified_name(), "datalink/tag");
+        assert!(tag.description().is_some());
+    }
+
+    #[test]
+    fn typed_is_zst() {
+        assert_eq!(size_of_val(&net::PORT), 0);
+        assert_eq!(net::PORT.get_id(), Some(net::PortType::ID));
+    }
+
+    #[test]
+    fn unique_ids() {
+        let terms: Vec<_> = registry::vocabularies()
+            .into_iter()
+            .flat_map(Vocabulary::terms)
+            .collect();
+
+        for (i, a) in terms.iter().enumerate() {
+            for b in &terms[i + 1..] {
+                assert_ne!(a.id(), b.id(), "{a} and {b} share an ID");
+            }
+        }
+    }
 }
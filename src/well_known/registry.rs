@@ -0,0 +1,109 @@
+//! Global registry of [`Vocabulary`]s for looking up well-known terms at runtime
+//!
+//! The vocabularies of this crate are always registered.
+//! Others have to be [`register`]ed before their terms can be found.
+
+use std::sync::{PoisonError, RwLock};
+
+use super::{Term, Vocabulary};
+use crate::id::ID;
+
+static BUILTIN: [&Vocabulary; 3] = [
+    &super::VOCABULARY,
+    &super::fs::VOCABULARY,
+    &super::net::VOCABULARY,
+];
+
+static REGISTERED: RwLock<Vec<&'static Vocabulary>> = RwLock::new(Vec::new());
+
+/// Register a vocabulary
+///
+/// Returns `false` if a vocabulary with the same namespace is already registered.
+#[inline]
+pub fn register(vocabulary: &'static Vocabulary) -> bool {
+    let mut registered = REGISTERED.write().unwrap_or_else(PoisonError::into_inner);
+    let namespace = vocabulary.namespace();
+    if BUILTIN
+        .iter()
+        .chain(registered.iter())
+        .any(|v| v.namespace() == namespace)
+    {
+        return false;
+    }
+    registered.push(vocabulary);
+    true
+}
+
+/// All known vocabularies, the builtin ones first
+#[inline]
+#[must_use]
+pub fn vocabularies() -> Vec<&'static Vocabulary> {
+    let registered = REGISTERED.read().unwrap_or_else(PoisonError::into_inner);
+    BUILTIN.iter().chain(registered.iter()).copied().collect()
+}
+
+/// Look up a term by its `ID`
+///
+/// ```rust
+/// use datalink::well_known::{registry, TagType, WellKnown};
+///
+/// let term = registry::lookup(TagType::ID).unwrap();
+/// assert_eq!(term.symbol(), "TAG");
+/// ```
+#[inline]
+#[must_use]
+pub fn lookup(id: ID) -> Option<&'static Term> {
+    find(|term| term.id() == id)
+}
+
+/// Look up a term by its namespaced name, like `datalink/tag`
+#[inline]
+#[must_use]
+pub fn lookup_name(qualified_name: &str) -> Option<&'static Term> {
+    let (namespace, name) = qualified_name.rsplit_once('/')?;
+    find(|term| term.namespace() == namespace && term.name() == name)
+}
+
+fn find(f: impl Fn(&Term) -> bool) -> Option<&'static Term> {
+    if let Some(term) = BUILTIN.iter().flat_map(|v| v.terms()).find(|t| f(t)) {
+        return Some(term);
+    }
+    let registered = REGISTERED.read().unwrap_or_else(PoisonError::into_inner);
+    registered.iter().flat_map(|v| v.terms()).find(|t| f(t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::well_known::{net::PortType, WellKnown};
+
+    #[allow(unreachable_pub, dead_code)]
+    mod custom {
+        crate::well_known! {
+            namespace = "test/custom";
+
+            FOO: FooType { name = "foo" },
+        }
+    }
+
+    #[test]
+    fn builtin() {
+        assert_eq!(lookup(PortType::ID).unwrap().symbol(), "PORT");
+        assert_eq!(lookup_name("datalink/net/port").unwrap().id(), PortType::ID);
+        assert!(lookup_name("datalink/port").is_none());
+    }
+
+    #[test]
+    fn custom() {
+        assert!(lookup(custom::FooType::ID).is_none());
+
+        assert!(register(&custom::VOCABULARY));
+        assert!(!register(&custom::VOCABULARY));
+
+        assert_eq!(lookup(custom::FooType::ID).unwrap().symbol(), "FOO");
+        assert!(lookup_name("test/custom/foo").is_some());
+        assert!(vocabularies()
+            .iter()
+            .any(|v| v.namespace() == "test/custom"));
+    }
+}